@@ -0,0 +1,96 @@
+use crate::parse::ParseError;
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+pub type Part = u8;
+
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub run: fn(&str, &[Part]) -> Result<Run, ParseError>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            run: run::<S>,
+        }
+    }
+    pub fn input_path(&self) -> String {
+        format!("aoc24{:02}/input.txt", self.day)
+    }
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+    let answers = parts.iter().map(|&part| {
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&input).to_string(),
+            _ => S::part2(&input).to_string(),
+        };
+        Answer { part, answer, elapsed: start.elapsed() }
+    }).collect();
+    Ok(Run { parse, answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::number;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 99;
+        type Input = Vec<u32>;
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace()
+                .map(|s| number(input, s))
+                .collect()
+        }
+        fn part1(input: &Self::Input) -> impl Display {
+            input.iter().sum::<u32>()
+        }
+        fn part2(input: &Self::Input) -> impl Display {
+            input.iter().product::<u32>()
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let day = Day::of::<Sum>();
+        assert_eq!(day.day, 99);
+        assert_eq!(day.input_path(), "aoc2499/input.txt");
+        let r = (day.run)("2 3 4", &[1, 2]).unwrap();
+        let answers: Vec<_> = r.answers.iter().map(|a| (a.part, a.answer.as_str())).collect();
+        assert_eq!(answers, [(1, "9"), (2, "24")]);
+        assert!((day.run)("2 x", &[1]).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::grid::Pos;
+use Direction::*;
+
+/* compass headings on a grid where y grows southwards */
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [North, East, South, West];
+    pub const ALL: [Direction; 8] = [North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest];
+
+    fn from_index(i: usize) -> Direction {
+        Self::ALL[i % 8]
+    }
+    fn index(&self) -> usize {
+        *self as usize
+    }
+    pub fn is_cardinal(&self) -> bool {
+        self.index().is_multiple_of(2)
+    }
+    /* rotate by 90 degrees */
+    pub fn left(&self) -> Direction {
+        Self::from_index(self.index() + 6)
+    }
+    pub fn right(&self) -> Direction {
+        Self::from_index(self.index() + 2)
+    }
+    /* rotate by 45 degrees */
+    pub fn left45(&self) -> Direction {
+        Self::from_index(self.index() + 7)
+    }
+    pub fn right45(&self) -> Direction {
+        Self::from_index(self.index() + 1)
+    }
+    pub fn reverse(&self) -> Direction {
+        Self::from_index(self.index() + 4)
+    }
+    /* smallest rotation to face `to`, in steps of 45 degrees (0..=4) */
+    pub fn angle(&self, to: &Direction) -> usize {
+        let d = (to.index() + 8 - self.index()) % 8;
+        d.min(8 - d)
+    }
+    pub fn pos(&self) -> Pos {
+        let (x, y) = match self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (-1, -1),
+        };
+        Pos { x, y }
+    }
+    /* heading of a unit step, any other offset has none */
+    pub fn from_pos(p: Pos) -> Option<Direction> {
+        Self::ALL.into_iter().find(|d| d.pos() == p)
+    }
+    /* arrow markers as used by the puzzle maps: '^', '>', 'v', '<' */
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(North),
+            '>' => Some(East),
+            'v' => Some(South),
+            '<' => Some(West),
+            _ => None,
+        }
+    }
+    pub fn to_char(&self) -> char {
+        match self {
+            North => '^',
+            NorthEast => '/',
+            East => '>',
+            SouthEast => '\\',
+            South => 'v',
+            SouthWest => '/',
+            West => '<',
+            NorthWest => '\\',
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl From<Direction> for Pos {
+    fn from(d: Direction) -> Pos {
+        d.pos()
+    }
+}
+
+impl TryFrom<Pos> for Direction {
+    type Error = Pos;
+    fn try_from(p: Pos) -> Result<Direction, Pos> {
+        Direction::from_pos(p).ok_or(p)
+    }
+}
+
+impl std::ops::Add<Direction> for Pos {
+    type Output = Pos;
+    fn add(self, d: Direction) -> Pos {
+        self + d.pos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(North.right(), East);
+        assert_eq!(North.left(), West);
+        assert_eq!(West.right().right(), East);
+        assert_eq!(NorthWest.right45(), North);
+        assert_eq!(North.left45(), NorthWest);
+        assert_eq!(SouthEast.reverse(), NorthWest);
+        assert_eq!(Direction::CARDINAL.map(|d| d.right()), [East, South, West, North]);
+        assert_eq!(East.angle(&East), 0);
+        assert_eq!(East.angle(&North), 2);
+        assert_eq!(North.angle(&East), 2);
+        assert_eq!(East.angle(&West), 4);
+        assert_eq!(NorthWest.angle(&NorthEast), 2);
+    }
+
+    #[test]
+    fn test_pos() {
+        let p = Pos { x: 3, y: 3 };
+        assert_eq!(p + North, Pos { x: 3, y: 2 });
+        assert_eq!(p + SouthWest, Pos { x: 2, y: 4 });
+        for d in Direction::ALL {
+            assert_eq!(Direction::try_from(d.pos()), Ok(d));
+            assert_eq!(-d.pos(), d.reverse().pos());
+        }
+        assert_eq!(Direction::try_from(Pos { x: 2, y: 0 }), Err(Pos { x: 2, y: 0 }));
+        assert_eq!(Direction::from_char('v'), Some(South));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+}
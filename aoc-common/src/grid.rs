@@ -0,0 +1,531 @@
+use crate::parse::ParseError;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl std::hash::Hash for Pos {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (self.x ^ (self.y << 32)).hash(state);
+    }
+}
+
+impl std::fmt::Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "'P{{{},{}}}'", self.x, self.y)
+    }
+}
+
+impl std::ops::Add for Pos {
+    type Output = Pos;
+    fn add(self, p: Pos) -> Pos {
+        Pos{x: self.x + p.x, y: self.y + p.y}
+    }
+}
+
+impl std::ops::Sub for Pos {
+    type Output = Pos;
+    fn sub(self, p: Pos) -> Pos {
+        Pos{x: self.x - p.x, y: self.y - p.y}
+    }
+}
+
+impl std::ops::Neg for Pos {
+    type Output = Pos;
+    fn neg(self) -> Pos {
+        Pos{x: -self.x, y: -self.y}
+    }
+}
+
+impl std::ops::Mul<isize> for Pos {
+    type Output = Pos;
+    fn mul(self, n: isize) -> Pos {
+        Pos{x: self.x * n, y: self.y * n}
+    }
+}
+
+impl std::ops::AddAssign for Pos {
+    fn add_assign(&mut self, p: Pos) {
+        *self = *self + p;
+    }
+}
+
+impl std::ops::SubAssign for Pos {
+    fn sub_assign(&mut self, p: Pos) {
+        *self = *self - p;
+    }
+}
+
+impl Pos {
+    pub fn from<T: Into<isize>>(x: T, y: T) -> Pos {
+        Pos {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+    /* wrap into 0..width, 0..height, for any distance outside the bounds */
+    pub fn wrap_in(&self, width: isize, height: isize) -> Pos {
+        Pos { x: self.x.rem_euclid(width), y: self.y.rem_euclid(height) }
+    }
+    pub fn manhattan(&self, p: &Pos) -> usize {
+        (self.x - p.x).unsigned_abs() + (self.y - p.y).unsigned_abs()
+    }
+    pub fn chebyshev(&self, p: &Pos) -> usize {
+        (self.x - p.x).unsigned_abs().max((self.y - p.y).unsigned_abs())
+    }
+    pub fn is_line(p1: Pos, p2: Pos, p3: Pos) -> bool {
+        let dxc = p1.x - p2.x;
+        let dyc = p1.y - p2.y;
+
+        let dxl = p3.x - p2.x;
+        let dyl = p3.y - p2.y;
+
+        let cross = dxc * dyl - dyc * dxl;
+        cross == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Area {
+    pub from: Pos,
+    pub to: Pos,
+}
+
+impl Area {
+    pub fn from(from: Pos, to: Pos) -> Self {
+        Area { from, to, }
+    }
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.from.x <= pos.x &&
+        self.to.x >= pos.x &&
+        self.from.y <= pos.y &&
+        self.to.y >= pos.y
+    }
+}
+
+pub struct GridIterator<'a, T> {
+    curr: Idx,
+    grid: &'a Grid<T>,
+}
+
+impl<T: Block> Iterator for GridIterator<'_, T> {
+    type Item = (Pos, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.curr;
+        let c = *self.grid.grid.get(i.0)?;
+        self.curr = Idx(i.0 + 1);
+        Some((self.grid.pos(i), c))
+    }
+}
+
+pub trait Block: Copy {
+    const EXPECTED: &'static str = "grid cell";
+    fn empty() -> Self;
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+impl Block for char {
+    fn empty() -> Self {
+        '.'
+    }
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+impl Block for u8 {
+    const EXPECTED: &'static str = "digit";
+    fn empty() -> Self {
+        0
+    }
+    fn from_char(c: char) -> Option<Self> {
+        c.to_digit(10).map(|d| d as u8)
+    }
+    fn to_char(&self) -> char {
+        char::from_digit(*self as u32, 10).unwrap_or('?')
+    }
+}
+
+/* index into the flat cell storage of a grid, row by row */
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Idx(pub usize);
+
+/* a toroidal grid continues on the opposite edge */
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Default)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    Toroidal,
+}
+
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Grid<T = char> {
+    pub width: isize,
+    pub height: isize,
+    pub topology: Topology,
+    grid: Vec<T>,
+}
+
+impl<T: Block> Grid<T> {
+    pub fn new(
+        width: isize,
+        height: isize,
+    ) -> Self {
+        let size = usize::try_from(width * height).unwrap();
+        Grid {
+            width,
+            height,
+            topology: Topology::Bounded,
+            grid: vec![T::empty(); size],
+        }
+    }
+    pub fn toroidal(
+        width: isize,
+        height: isize,
+    ) -> Self {
+        Self::new(width, height).with_topology(Topology::Toroidal)
+    }
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+    fn parse_grid_size(input: &str) -> Option<(isize, isize)> {
+        let (mut width, mut height) = (0, 0);
+        for line in input.lines() {
+            if line.is_empty() { continue; }
+            height += 1;
+            if width == 0 { width = line.chars().count() }
+        }
+        if height == 0 { return None; }
+        Some((isize::try_from(width).unwrap(), isize::try_from(height).unwrap()))
+    }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let size = Self::parse_grid_size(input)
+            .ok_or(ParseError::end(input, "grid rows"))?;
+        let mut g = Self::new(size.0, size.1);
+
+        let mut y = 0;
+        for ln in input.lines() {
+            if ln.is_empty() { continue; }
+            if ln.chars().count() != g.width as usize {
+                return Err(ParseError::at(input, ln, format!("row of width {}", g.width)));
+            }
+            for (x, (i, c)) in ln.char_indices().enumerate() {
+                g.grid[y * g.width as usize + x] = T::from_char(c)
+                    .ok_or(ParseError::at(input, &ln[i..i + c.len_utf8()], T::EXPECTED))?;
+            }
+            y += 1;
+        }
+        Ok(g)
+    }
+    pub fn neighbors(&self, p: &Pos) -> HashMap<Pos, T> {
+        let mut n = HashMap::with_capacity(8);
+        for i in -1..=1 {
+            for j in -1..=1 {
+                let np = Pos::from(p.x + i, p.y + j);
+                if *p != np {
+                    self.locate(&np).and_then(|np| n.insert(np, self.get(&np)?));
+                }
+            }
+        }
+        n
+    }
+    pub fn neighbors_xy(&self, p: &Pos) -> HashMap<Pos, T> {
+        let mut n = HashMap::with_capacity(4);
+        [
+            Pos::from(p.x-1, p.y),
+            Pos::from(p.x+1, p.y),
+            Pos::from(p.x, p.y-1),
+            Pos::from(p.x, p.y+1),
+        ].into_iter().for_each(|np| {
+            self.locate(&np).and_then(|np| n.insert(np, self.get(&np)?));
+        });
+        n
+    }
+    /* the cell position `p` refers to, wrapped around on a toroidal grid */
+    pub fn locate(&self, p: &Pos) -> Option<Pos> {
+        match self.topology {
+            Topology::Bounded => self.contains(p).then_some(*p),
+            Topology::Toroidal => Some(self.wrap(*p)),
+        }
+    }
+    pub fn wrap(&self, p: Pos) -> Pos {
+        p.wrap_in(self.width, self.height)
+    }
+    pub fn idx(&self, p: &Pos) -> Option<Idx> {
+        let p = self.locate(p)?;
+        Some(Idx((p.y * self.width + p.x) as usize))
+    }
+    pub fn pos(&self, i: Idx) -> Pos {
+        let w = self.width as usize;
+        Pos { x: (i.0 % w) as isize, y: (i.0 / w) as isize }
+    }
+    pub fn get(&self, p: &Pos) -> Option<T> {
+        self.idx(p).map(|i| self.grid[i.0])
+    }
+    pub fn set(&mut self, p: Pos, c: T) {
+        let i = self.idx(&p).expect("position on the grid");
+        self.grid[i.0] = c
+    }
+    pub fn row(&self, y: isize) -> &[T] {
+        let w = self.width as usize;
+        let y = usize::try_from(y).unwrap();
+        &self.grid[y * w..(y + 1) * w]
+    }
+    pub fn column(&self, x: isize) -> impl Iterator<Item = T> + '_ {
+        let x = usize::try_from(x).unwrap();
+        assert!(x < self.width as usize, "column out of the grid");
+        self.grid[x..].iter().step_by(self.width as usize).copied()
+    }
+    pub fn cells(&self) -> &[T] {
+        &self.grid
+    }
+    pub fn dist(p1: Pos, p2: Pos) -> usize {
+        p1.manhattan(&p2)
+    }
+    pub fn contains(&self, p: &Pos) -> bool {
+        self.topology == Topology::Toroidal || !(p.x < 0 || p.y < 0
+        || p.x >= self.width || p.y >= self.height)
+    }
+    pub fn print(&self) -> &Self {
+        println!("{}", self);
+        self
+    }
+    pub fn iter(&self) -> GridIterator<'_, T> {
+        GridIterator {
+            curr: Idx(0),
+            grid: self,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+/* connected cells of equal value, labeled in reading order of their first cell */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region<T> {
+    pub label: usize,
+    pub cell: T,
+    pub cells: HashSet<Pos>,
+}
+
+impl<T> Region<T> {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn perimeter(&self) -> usize {
+        self.cells.iter().map(|p| {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)].into_iter()
+                .filter(|(dx, dy)| !self.cells.contains(&Pos::from(p.x + dx, p.y + dy)))
+                .count()
+        }).sum()
+    }
+    /* a polygon has as many corners as sides */
+    pub fn corners(&self) -> usize {
+        let has = |p: &Pos, dx, dy| self.cells.contains(&Pos::from(p.x + dx, p.y + dy));
+        self.cells.iter().map(|p| {
+            [(-1, -1), (1, -1), (1, 1), (-1, 1)].into_iter()
+                .filter(|&(dx, dy)| {
+                    let (h, v) = (has(p, dx, 0), has(p, 0, dy));
+                    (!h && !v) || (h && v && !has(p, dx, dy))
+                })
+                .count()
+        }).sum()
+    }
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+    pub fn bounds(&self) -> Area {
+        let mut it = self.cells.iter();
+        let first = *it.next().expect("regions are never empty");
+        it.fold(Area::from(first, first), |a, p| Area::from(
+            Pos::from(a.from.x.min(p.x), a.from.y.min(p.y)),
+            Pos::from(a.to.x.max(p.x), a.to.y.max(p.y)),
+        ))
+    }
+}
+
+impl<T: Block + PartialEq> Grid<T> {
+    pub fn regions(&self, conn: Connectivity) -> Vec<Region<T>> {
+        let mut labels: Vec<Option<usize>> = vec![None; self.grid.len()];
+        let mut regions = Vec::new();
+        for (i, &cell) in self.grid.iter().enumerate() {
+            if labels[i].is_some() { continue; }
+            let label = regions.len();
+            let mut cells = HashSet::new();
+            let mut todo = vec![self.pos(Idx(i))];
+            labels[i] = Some(label);
+            while let Some(p) = todo.pop() {
+                cells.insert(p);
+                let n = match conn {
+                    Connectivity::Four => self.neighbors_xy(&p),
+                    Connectivity::Eight => self.neighbors(&p),
+                };
+                for (np, c) in n {
+                    let ni = self.idx(&np).unwrap().0;
+                    if c == cell && labels[ni].is_none() {
+                        labels[ni] = Some(label);
+                        todo.push(np);
+                    }
+                }
+            }
+            regions.push(Region { label, cell, cells });
+        }
+        regions
+    }
+}
+
+impl<T: Block> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for y in 0..self.height {
+            for col in self.row(y) {
+                write!(f, "{}", col.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> std::ops::Index<Idx> for Grid<T> {
+    type Output = T;
+    fn index(&self, i: Idx) -> &T {
+        &self.grid[i.0]
+    }
+}
+
+impl<T> std::ops::IndexMut<Idx> for Grid<T> {
+    fn index_mut(&mut self, i: Idx) -> &mut T {
+        &mut self.grid[i.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let g: Grid = Grid::parse("\nab\ncd\n").unwrap();
+        assert_eq!(g.width, 2);
+        assert_eq!(g.height, 2);
+        assert_eq!(g.iter().map(|(_, c)| c).collect::<String>(), "abcd");
+        assert_eq!(g.iter().last(), Some((Pos { x: 1, y: 1 }, 'd')));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(Grid::<char>::parse("\n\n"), Err(ParseError::new(3, 1, "", "grid rows")));
+        assert_eq!(Grid::<char>::parse("abc\nde\nfgh"), Err(ParseError::new(2, 1, "de", "row of width 3")));
+        assert_eq!(Grid::<u8>::parse("012\n3x5"), Err(ParseError::new(2, 2, "x", "digit")));
+    }
+
+    #[test]
+    fn test_typed() {
+        let mut g = Grid::<u8>::parse("012\n345").unwrap();
+        assert_eq!(g.get(&Pos { x: 2, y: 1 }), Some(5));
+        g.set(Pos { x: 0, y: 0 }, 9);
+        assert_eq!(g.neighbors_xy(&Pos { x: 1, y: 0 }).values().sum::<u8>(), 9 + 2 + 4);
+        assert_eq!(g.iter().map(|(_, h)| h as usize).sum::<usize>(), 9 + 1 + 2 + 3 + 4 + 5);
+        assert_eq!(g.to_string(), "912\n345\n");
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut g: Grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(g.row(1), &['d', 'e', 'f']);
+        assert_eq!(g.column(2).collect::<String>(), "cf");
+        let i = g.idx(&Pos { x: 1, y: 1 }).unwrap();
+        assert_eq!(i, Idx(4));
+        assert_eq!(g.pos(i), Pos { x: 1, y: 1 });
+        assert_eq!(g[i], 'e');
+        g[i] = 'x';
+        assert_eq!(g.get(&Pos { x: 1, y: 1 }), Some('x'));
+        assert_eq!(g.idx(&Pos { x: 3, y: 0 }), None);
+        assert_eq!(g.cells().len(), 6);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g: Grid = Grid::parse("abc\ndef\nghi").unwrap();
+        assert_eq!(g.neighbors(&Pos { x: 1, y: 1 }).len(), 8);
+        assert_eq!(g.neighbors(&Pos { x: 0, y: 0 }).len(), 3);
+        assert_eq!(g.neighbors_xy(&Pos { x: 0, y: 0 }).len(), 2);
+        assert_eq!(g.neighbors_xy(&Pos { x: 1, y: 1 }).get(&Pos { x: 1, y: 0 }), Some(&'b'));
+    }
+
+    #[test]
+    fn test_regions() {
+        let g: Grid = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let r = g.regions(Connectivity::Four);
+        assert_eq!(r.len(), 5);
+        let stats: Vec<_> = r.iter().map(|r| (r.cell, r.area(), r.perimeter(), r.sides())).collect();
+        assert_eq!(stats, vec![
+            ('A', 4, 10, 4),
+            ('B', 4, 8, 4),
+            ('C', 4, 10, 8),
+            ('D', 1, 4, 4),
+            ('E', 3, 8, 4),
+        ]);
+        assert_eq!(r[2].bounds(), Area::from(Pos { x: 2, y: 1 }, Pos { x: 3, y: 3 }));
+    }
+
+    #[test]
+    fn test_regions_nested() {
+        let g: Grid = Grid::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+        let r = g.regions(Connectivity::Four);
+        assert_eq!(r.len(), 5);
+        assert_eq!((r[0].area(), r[0].perimeter(), r[0].sides()), (21, 36, 20));
+        let g: Grid = Grid::parse("X.\n.X").unwrap();
+        assert_eq!(g.regions(Connectivity::Four).len(), 4);
+        assert_eq!(g.regions(Connectivity::Eight).len(), 2);
+    }
+
+    #[test]
+    fn test_pos_ops() {
+        let (a, b) = (Pos { x: 1, y: -2 }, Pos { x: 4, y: 2 });
+        assert_eq!(a + b, Pos { x: 5, y: 0 });
+        assert_eq!(b - a, Pos { x: 3, y: 4 });
+        assert_eq!(-a, Pos { x: -1, y: 2 });
+        assert_eq!(a * 3, Pos { x: 3, y: -6 });
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+    }
+
+    #[test]
+    fn test_toroidal() {
+        assert_eq!(Pos { x: -1, y: 23 }.wrap_in(11, 7), Pos { x: 10, y: 2 });
+        assert_eq!((Pos { x: 2, y: 4 } + Pos { x: 2, y: -3 } * 5).wrap_in(11, 7), Pos { x: 1, y: 3 });
+        let g: Grid = Grid::parse("abc\ndef").unwrap().with_topology(Topology::Toroidal);
+        assert!(g.contains(&Pos { x: -4, y: 9 }));
+        assert_eq!(g.get(&Pos { x: -1, y: -1 }), Some('f'));
+        assert_eq!(g.get(&Pos { x: 3, y: 2 }), Some('a'));
+        let n = g.neighbors_xy(&Pos { x: 0, y: 0 });
+        assert_eq!(n.get(&Pos { x: 2, y: 0 }), Some(&'c'));
+        assert_eq!(g.neighbors(&Pos { x: 1, y: 0 }).len(), 5);
+        assert_eq!(Grid::<char>::toroidal(3, 3).iter().count(), 9);
+    }
+
+    #[test]
+    fn test_area() {
+        let a = Area::from(Pos { x: 1, y: 1 }, Pos { x: 2, y: 3 });
+        assert!(a.contains(Pos { x: 2, y: 3 }));
+        assert!(!a.contains(Pos { x: 0, y: 3 }));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod day;
+pub mod direction;
+pub mod grid;
+pub mod parse;
+pub mod progress;
+pub mod search;
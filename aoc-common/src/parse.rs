@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+    /*
+     * `token` has to be a slice of `input`, as handed out by `lines()`,
+     * `split()`, `trim()` or regex captures, so that its position can be
+     * recovered from the pointer offset.
+     */
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "token is not part of the input");
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            token,
+            expected,
+        )
+    }
+    pub fn end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        match self.text.as_str() {
+            "" => write!(f, "found end of input"),
+            text => write!(f, "found '{}'", text),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::at(input, token, "number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "1 2\n3 x4\n";
+        let token = &input[6..8];
+        assert_eq!(ParseError::at(input, token, "number"), ParseError::new(2, 3, "x4", "number"));
+        assert_eq!(
+            ParseError::at(input, token, "number").to_string(),
+            "line 2, column 3: expected number, found 'x4'");
+    }
+
+    #[test]
+    fn test_end() {
+        let input = "ab\ncd";
+        assert_eq!(ParseError::end(input, "guard"), ParseError::new(2, 3, "", "guard"));
+        assert_eq!(
+            ParseError::end(input, "guard").to_string(),
+            "line 2, column 3: expected guard, found end of input");
+    }
+
+    #[test]
+    fn test_number() {
+        let input = "12 -3 x";
+        let mut tokens = input.split(' ');
+        assert_eq!(number::<u8>(input, tokens.next().unwrap()), Ok(12));
+        assert_eq!(number::<i8>(input, tokens.next().unwrap()), Ok(-3));
+        assert_eq!(number::<u8>(input, tokens.next().unwrap()), Err(ParseError::new(1, 7, "x", "number")));
+    }
+}
@@ -0,0 +1,194 @@
+use std::cell::{Cell, RefCell};
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/*
+ * Long running solvers report to the reporter installed for the current
+ * thread, and stop early with what they have once it is cancelled.
+ */
+pub trait Progress {
+    /* `done` out of `total` units of `task`, searches do not know the total */
+    fn report(&self, _task: &str, _done: usize, _total: Option<usize>) {}
+    fn cancelled(&self) -> bool {
+        false
+    }
+}
+
+impl<P: Progress + ?Sized> Progress for Box<P> {
+    fn report(&self, task: &str, done: usize, total: Option<usize>) {
+        (**self).report(task, done, total)
+    }
+    fn cancelled(&self) -> bool {
+        (**self).cancelled()
+    }
+}
+
+pub struct Quiet;
+
+impl Progress for Quiet {}
+
+/* cancels from anywhere, e.g. another thread holding a clone */
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Progress for Cancel {
+    fn cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    deadline: Instant,
+}
+
+impl Budget {
+    pub fn new(budget: Duration) -> Self {
+        Budget { deadline: Instant::now() + budget }
+    }
+}
+
+impl Progress for Budget {
+    fn cancelled(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+}
+
+/* live progress line on stderr, cancelled whenever `inner` is */
+pub struct Bar<P: Progress = Quiet> {
+    inner: P,
+    drawn: Cell<Option<Instant>>,
+}
+
+impl<P: Progress> Bar<P> {
+    const WIDTH: usize = 30;
+    const EVERY: Duration = Duration::from_millis(100);
+
+    pub fn new(inner: P) -> Self {
+        Bar { inner, drawn: Cell::new(None) }
+    }
+    fn line(task: &str, done: usize, total: Option<usize>) -> String {
+        match total {
+            Some(total) => {
+                let n = (done * Self::WIDTH).checked_div(total).unwrap_or(Self::WIDTH).min(Self::WIDTH);
+                format!("{task} [{}{}] {done}/{total}", "#".repeat(n), ".".repeat(Self::WIDTH - n))
+            },
+            None => format!("{task} {done}"),
+        }
+    }
+}
+
+impl<P: Progress> Progress for Bar<P> {
+    fn report(&self, task: &str, done: usize, total: Option<usize>) {
+        self.inner.report(task, done, total);
+        let now = Instant::now();
+        if self.drawn.get().is_some_and(|t| now < t + Self::EVERY) { return; }
+        self.drawn.set(Some(now));
+        eprint!("\r\x1b[K{}", Self::line(task, done, total));
+        let _ = std::io::stderr().flush();
+    }
+    fn cancelled(&self) -> bool {
+        self.inner.cancelled()
+    }
+}
+
+impl<P: Progress> Drop for Bar<P> {
+    fn drop(&mut self) {
+        if self.drawn.get().is_some() {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Rc<dyn Progress>> = RefCell::new(Rc::new(Quiet));
+}
+
+/* puts the previous reporter back, even when unwinding */
+struct Restore(Option<Rc<dyn Progress>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        if let Some(prev) = self.0.take() {
+            CURRENT.with(|c| c.replace(prev));
+        }
+    }
+}
+
+/* runs `f` with `p` as the reporter of this thread */
+pub fn with<R>(p: impl Progress + 'static, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(Some(CURRENT.with(|c| c.replace(Rc::new(p)))));
+    f()
+}
+
+pub fn current() -> Rc<dyn Progress> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with() {
+        assert!(!current().cancelled());
+        let c = Cancel::new();
+        let r = with(c.clone(), || {
+            let before = current().cancelled();
+            c.cancel();
+            (before, current().cancelled())
+        });
+        assert_eq!(r, (false, true));
+        assert!(!current().cancelled());
+        assert!(with(Budget::new(Duration::ZERO), || current().cancelled()));
+        assert!(!with(Bar::new(Budget::new(Duration::from_secs(60))), || current().cancelled()));
+    }
+
+    #[test]
+    fn test_with_panic() {
+        let c = Cancel::new();
+        c.cancel();
+        let r = std::panic::catch_unwind(|| with(c, || -> () { panic!("solver failed") }));
+        assert!(r.is_err());
+        assert!(!current().cancelled());
+    }
+
+    /* counts the reports it gets */
+    struct Count(Rc<Cell<usize>>);
+
+    impl Progress for Count {
+        fn report(&self, _task: &str, _done: usize, _total: Option<usize>) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_bar_forwards() {
+        let n = Rc::new(Cell::new(0));
+        let bar = Bar::new(Count(n.clone()));
+        for i in 0..100 {
+            bar.report("rows", i, Some(100));
+        }
+        drop(bar);
+        assert_eq!(n.get(), 100);
+    }
+
+    #[test]
+    fn test_bar() {
+        assert_eq!(Bar::<Quiet>::line("rows", 5, Some(10)), format!("rows [{}{}] 5/10", "#".repeat(15), ".".repeat(15)));
+        assert_eq!(Bar::<Quiet>::line("rows", 0, Some(0)), format!("rows [{}] 0/0", "#".repeat(30)));
+        assert_eq!(Bar::<Quiet>::line("search", 42, None), "search 42");
+    }
+}
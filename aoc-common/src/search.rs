@@ -0,0 +1,309 @@
+use crate::progress::{self, Progress};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/*
+ * Result of a search: the best known cost of every reached state, all
+ * predecessors a state can be reached from at that cost, and the goal
+ * states reached at the best cost. Start states have no predecessors.
+ * A search cancelled through the progress reporter is not `complete`.
+ */
+#[derive(Clone, Debug)]
+pub struct Paths<S, C> {
+    pub cost: HashMap<S, C>,
+    pub preds: HashMap<S, Vec<S>>,
+    pub end: Vec<S>,
+    pub complete: bool,
+}
+
+/* states expanded between progress reports */
+const REPORT_EVERY: usize = 1 << 12;
+
+impl<S: Copy + Eq + Hash, C: Copy + Ord> Paths<S, C> {
+    fn new() -> Self {
+        Paths {
+            cost: HashMap::new(),
+            preds: HashMap::new(),
+            end: Vec::new(),
+            complete: true,
+        }
+    }
+    /* reports every so often, and tells whether to carry on */
+    fn tick(&mut self, progress: &dyn Progress, expanded: usize) -> bool {
+        if !expanded.is_multiple_of(REPORT_EVERY) { return true; }
+        progress.report("search", expanded, None);
+        self.complete = !progress.cancelled();
+        self.complete
+    }
+    pub fn best(&self) -> Option<C> {
+        self.end.first().map(|s| self.cost[s])
+    }
+    /* one best path from a start state to the first goal state */
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(*self.end.first()?)
+    }
+    pub fn path_to(&self, s: S) -> Option<Vec<S>> {
+        self.cost.get(&s)?;
+        let mut path = vec![s];
+        while let Some(p) = self.preds.get(path.last().unwrap()).and_then(|p| p.first()) {
+            path.push(*p);
+        }
+        path.reverse();
+        Some(path)
+    }
+    /* every state on some best path to any goal state */
+    pub fn nodes(&self) -> HashSet<S> {
+        self.nodes_to(self.end.iter().copied())
+    }
+    pub fn nodes_to(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut todo: Vec<S> = ends.into_iter().filter(|s| self.cost.contains_key(s)).collect();
+        while let Some(s) = todo.pop() {
+            if !seen.insert(s) { continue; }
+            if let Some(p) = self.preds.get(&s) {
+                todo.extend(p.iter().copied());
+            }
+        }
+        seen
+    }
+    /* number of distinct best paths from any start state to `s` */
+    pub fn count(&self, s: S) -> usize {
+        let mut nodes: Vec<S> = self.nodes_to([s]).into_iter().collect();
+        nodes.sort_by_key(|n| self.cost[n]);
+        let mut counts: HashMap<S, usize> = HashMap::with_capacity(nodes.len());
+        for n in nodes {
+            let c = match self.preds.get(&n) {
+                Some(p) => p.iter().map(|p| counts[p]).sum(),
+                None => 1,
+            };
+            counts.insert(n, c);
+        }
+        counts.get(&s).copied().unwrap_or(0)
+    }
+}
+
+/* breadth-first search with unit edge costs */
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl Fn(&S) -> I,
+    goal: impl Fn(&S) -> bool,
+) -> Paths<S, usize>
+where
+    S: Copy + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut p = Paths::new();
+    let mut todo = VecDeque::new();
+    for s in starts {
+        p.cost.insert(s, 0);
+        todo.push_back(s);
+    }
+    let mut best = None;
+    let progress = progress::current();
+    let mut expanded = 0;
+    while let Some(s) = todo.pop_front() {
+        expanded += 1;
+        if !p.tick(progress.as_ref(), expanded) { break; }
+        let cost = p.cost[&s];
+        if best.is_some_and(|b| cost > b) { break; }
+        if goal(&s) {
+            best = Some(cost);
+            p.end.push(s);
+            continue;
+        }
+        for next in successors(&s) {
+            match p.cost.get(&next) {
+                Some(&c) if c == cost + 1 => p.preds.entry(next).or_default().push(s),
+                Some(_) => {},
+                None => {
+                    p.cost.insert(next, cost + 1);
+                    p.preds.insert(next, vec![s]);
+                    todo.push_back(next);
+                },
+            }
+        }
+    }
+    p
+}
+
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl Fn(&S) -> I,
+    goal: impl Fn(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Copy + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+/*
+ * `heuristic` must never overestimate the remaining cost to a goal. It
+ * need not be consistent, a state reached cheaper after its expansion is
+ * expanded again.
+ */
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl Fn(&S) -> I,
+    heuristic: impl Fn(&S) -> C,
+    goal: impl Fn(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Copy + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut p = Paths::new();
+    let mut todo = BinaryHeap::new();
+    for s in starts {
+        p.cost.insert(s, C::default());
+        todo.push(Entry { prio: heuristic(&s), cost: C::default(), state: s });
+    }
+    let mut done = HashSet::new();
+    let mut best = None;
+    let progress = progress::current();
+    while let Some(Entry { prio, cost, state }) = todo.pop() {
+        if best.is_some_and(|b| prio > b) { break; }
+        if cost > p.cost[&state] || !done.insert(state) { continue; }
+        if !p.tick(progress.as_ref(), done.len()) { break; }
+        if goal(&state) {
+            best = Some(cost);
+            p.end.push(state);
+            continue;
+        }
+        for (next, w) in successors(&state) {
+            let nc = cost + w;
+            match p.cost.get(&next) {
+                Some(&c) if nc > c => {},
+                Some(&c) if nc == c => p.preds.entry(next).or_default().push(state),
+                _ => {
+                    p.cost.insert(next, nc);
+                    p.preds.insert(next, vec![state]);
+                    done.remove(&next);
+                    todo.push(Entry { prio: nc + heuristic(&next), cost: nc, state: next });
+                },
+            }
+        }
+    }
+    p
+}
+
+/* min-heap entry ordered by priority only, so states need no `Ord` */
+struct Entry<S, C> {
+    prio: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.prio.cmp(&self.prio)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.prio == other.prio
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "
+#######
+#.....#
+#.###.#
+#.....#
+###.###
+#.....#
+#######
+";
+
+    fn open(g: &Grid, p: &Pos) -> Vec<Pos> {
+        g.neighbors_xy(p).into_iter().filter(|(_, c)| *c == '.').map(|(p, _)| p).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let g: Grid = Grid::parse(MAZE).unwrap();
+        let (start, end) = (Pos { x: 3, y: 1 }, Pos { x: 3, y: 5 });
+        let p = bfs([start], |p| open(&g, p), |p| *p == end);
+        assert_eq!(p.best(), Some(8));
+        assert_eq!(p.path().unwrap().len(), 9);
+        assert_eq!(p.count(end), 2);
+        assert_eq!(p.nodes().len(), 14);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let g: Grid = Grid::parse(MAZE).unwrap();
+        let (start, end) = (Pos { x: 3, y: 1 }, Pos { x: 3, y: 5 });
+        /* walking down is expensive */
+        let succ = |p: &Pos| {
+            let p = *p;
+            open(&g, &p).into_iter().map(move |n| (n, if n.y > p.y { 10 } else { 1 }))
+        };
+        let d = dijkstra([start], succ, |p| *p == end);
+        assert_eq!(d.best(), Some(44));
+        assert_eq!(d.count(end), 2);
+        let a = astar([start], succ, |p| Grid::<char>::dist(*p, end), |p| *p == end);
+        assert_eq!(a.best(), d.best());
+        assert_eq!(a.nodes(), d.nodes());
+    }
+
+    #[test]
+    fn test_inconsistent() {
+        /* 0 -> 1 -> 3 is cheapest, but the high estimate at 1 gets 3 expanded via 2 first */
+        let succ = |n: &u8| match n {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 3)],
+            3 => vec![(4, 5)],
+            _ => vec![],
+        };
+        let h = |n: &u8| if *n == 1 { 5 } else { 0 };
+        let a = astar([0u8], succ, h, |n| *n == 4);
+        assert_eq!(a.best(), Some(7));
+        assert_eq!(a.path(), Some(vec![0, 1, 3, 4]));
+        assert_eq!(a.preds[&3], vec![1]);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let p = dijkstra([0u8], |n| [(n + 1, 1usize)].into_iter().filter(|(n, _)| *n < 5), |n| *n == 9);
+        assert_eq!(p.best(), None);
+        assert_eq!(p.path(), None);
+        assert_eq!(p.cost.len(), 5);
+        assert_eq!(p.path_to(4), Some(vec![0, 1, 2, 3, 4]));
+        assert!(p.complete);
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let succ = |n: &u32| [(n + 1, 1usize)];
+        let p = progress::with(progress::Budget::new(std::time::Duration::ZERO), || {
+            dijkstra([0u32], succ, |n| *n == 1 << 20)
+        });
+        assert!(!p.complete);
+        assert_eq!(p.best(), None);
+        assert_eq!(p.cost.len(), REPORT_EVERY);
+        let p = progress::with(progress::Cancel::new(), || bfs([0u32], |n| [n + 1], |n| *n == 1 << 14));
+        assert!(p.complete);
+        assert_eq!(p.best(), Some(1 << 14));
+    }
+}
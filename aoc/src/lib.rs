@@ -0,0 +1,41 @@
+use aoc_common::day::Day;
+
+pub const DAYS: &[Day] = &[
+    Day::of::<aoc2401::Day01>(),
+    Day::of::<aoc2402::Day02>(),
+    Day::of::<aoc2403::Day03>(),
+    Day::of::<aoc2404::Day04>(),
+    Day::of::<aoc2405::Day05>(),
+    Day::of::<aoc2406::Day06>(),
+    Day::of::<aoc2407::Day07>(),
+    Day::of::<aoc2408::Day08>(),
+    Day::of::<aoc2409::Day09>(),
+    Day::of::<aoc2410::Day10>(),
+    Day::of::<aoc2411::Day11>(),
+    Day::of::<aoc2412::Day12>(),
+    Day::of::<aoc2413::Day13>(),
+    Day::of::<aoc2414::Day14>(),
+    Day::of::<aoc2415::Day15>(),
+    Day::of::<aoc2416::Day16>(),
+    Day::of::<aoc2417::Day17>(),
+    Day::of::<aoc2418::Day18>(),
+    Day::of::<aoc2419::Day19>(),
+    Day::of::<aoc2425::Day25>(),
+];
+
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+        assert_eq!(day(7).map(|d| d.day), Some(7));
+        assert!(day(12).is_some());
+        assert!(day(20).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use aoc::DAYS;
+use aoc_common::day::Day;
+use aoc_common::progress::{self, Bar, Budget, Progress, Quiet};
+use std::{
+    env,
+    fs,
+    process,
+    time::Duration,
+};
+
+const USAGE: &str = "usage: aoc run [--day N] [--part 1|2] [--input PATH] [--progress] [--budget SECONDS]";
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{e}\n{USAGE}");
+        process::exit(2);
+    });
+    let days: Vec<&Day> = DAYS.iter()
+        .filter(|d| args.day.is_none_or(|n| d.day == n))
+        .collect();
+    if days.is_empty() {
+        eprintln!("day {} is not implemented", args.day.unwrap_or_default());
+        process::exit(1);
+    }
+    run(&days, &args);
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    progress: bool,
+    budget: Option<Duration>,
+}
+
+fn parse_args(args: impl IntoIterator<Item=String>) -> Result<Args, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => {},
+        Some(cmd) => return Err(format!("unknown command '{cmd}'")),
+        None => return Err("missing command".to_string()),
+    }
+    let mut a = Args::default();
+    while let Some(arg) = args.next() {
+        if arg == "--progress" {
+            a.progress = true;
+            continue;
+        }
+        let value = args.next().ok_or(format!("missing value for {arg}"))?;
+        match arg.as_str() {
+            "--day" => {
+                a.day = Some(value.parse().map_err(|_| format!("invalid day '{value}'"))?);
+            },
+            "--part" => {
+                match value.as_str() {
+                    "1" | "2" => a.part = value.parse().ok(),
+                    _ => return Err(format!("invalid part '{value}'")),
+                }
+            },
+            "--input" => a.input = Some(value),
+            "--budget" => {
+                let secs: f64 = value.parse().map_err(|_| format!("invalid budget '{value}'"))?;
+                a.budget = Some(Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid budget '{value}'"))?);
+            },
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    if a.input.is_some() && a.day.is_none() {
+        return Err("--input requires --day".to_string());
+    }
+    Ok(a)
+}
+
+fn run(days: &[&Day], args: &Args) {
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let mut total = Duration::ZERO;
+    println!("{:>3} {:>5}  {:<20} {:>12}", "day", "part", "answer", "time");
+    for day in days {
+        let path = args.input.clone().unwrap_or_else(|| day.input_path());
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3} {:>5}  {path}: {e}", day.day, "-");
+                continue;
+            },
+        };
+        /* the budget is per day, the bar is drawn on stderr */
+        let budget = args.budget.map(Budget::new);
+        let reporter: Box<dyn Progress> = match (args.progress, budget) {
+            (true, Some(b)) => Box::new(Bar::new(b)),
+            (true, None) => Box::new(Bar::new(Quiet)),
+            (false, Some(b)) => Box::new(b),
+            (false, None) => Box::new(Quiet),
+        };
+        let r = match progress::with(reporter, || (day.run)(&input, &parts)) {
+            Ok(r) => r,
+            Err(e) => {
+                println!("{:>3} {:>5}  {path}: {e}", day.day, "-");
+                continue;
+            },
+        };
+        total += r.parse;
+        println!("{:>3} {:>5}  {:<20} {:>12}", day.day, "parse", "", format!("{:.2?}", r.parse));
+        for a in r.answers {
+            total += a.elapsed;
+            println!("{:>3} {:>5}  {:<20} {:>12}", day.day, a.part, a.answer, format!("{:.2?}", a.elapsed));
+        }
+        if budget.is_some_and(|b| b.cancelled()) {
+            println!("{:>3} {:>5}  budget of {:.2?} exceeded, answers may be partial", day.day, "-", args.budget.unwrap());
+        }
+    }
+    println!("{:>3} {:>5}  {:<20} {:>12}", "", "", "total", format!("{total:.2?}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_args() {
+        assert_eq!(args("run"), Ok(Args::default()));
+        assert_eq!(args("run --day 7 --part 2 --input x.txt"), Ok(Args {
+            day: Some(7),
+            part: Some(2),
+            input: Some("x.txt".to_string()),
+            ..Args::default()
+        }));
+        assert_eq!(args("run --progress --budget 1.5 --day 6"), Ok(Args {
+            day: Some(6),
+            progress: true,
+            budget: Some(Duration::from_millis(1500)),
+            ..Args::default()
+        }));
+    }
+
+    #[test]
+    fn test_args_invalid() {
+        assert!(args("").is_err());
+        assert!(args("walk").is_err());
+        assert!(args("run --day").is_err());
+        assert!(args("run --day x").is_err());
+        assert!(args("run --part 3").is_err());
+        assert!(args("run --input x.txt").is_err());
+        assert!(args("run --budget").is_err());
+        assert!(args("run --budget -1").is_err());
+        assert!(args("run --progress yes").is_err());
+    }
+}
@@ -1,37 +1,45 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file {file_path}");
-    let c = dist(parse(&input).unwrap());
-    let m = sim(parse(&input).unwrap());
-    println!("{c} / {m}");
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = (Vec<i32>, Vec<i32>);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        dist(input.clone())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        sim(input.clone())
+    }
 }
 
-fn parse(input: &str) -> Option<(Vec<i32>, Vec<i32>)> {
+fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut l: Vec<i32> = vec![];
     let mut r: Vec<i32> = vec![];
 
     for line in input.lines() {
-        match line
+        let line = line
             .trim_start()
-            .trim_end()
-            .split_once(' ')
-        {
+            .trim_end();
+        if line.is_empty() { continue; }
+        match line.split_once(' ') {
             Some((x,y)) => {
                 // println!("= {x} / {y}");
-                l.push(x.parse::<i32>().unwrap());
-                r.push(y.trim_start().parse::<i32>().unwrap());
+                l.push(number(input, x)?);
+                r.push(number(input, y.trim_start())?);
             },
             _ => {
-                // println!("x {line}");
-                continue;
+                return Err(ParseError::at(input, line, "two location ids"));
             },
         }
     }
-    Some((l, r))
+    Ok((l, r))
 }
 
 fn dist(lr: (Vec<i32>, Vec<i32>)) -> i64 {
@@ -94,4 +102,19 @@ fn test_sim() {
     assert_eq!(sim(parse(input).unwrap()), 31);
 }
 
+#[test]
+fn test_parse_error() {
+    let input = "
+3   4
+4   x
+";
+    assert_eq!(parse(input), Err(ParseError::new(3, 5, "x", "number")));
+    let input = "
+3   4
+7
+";
+    assert_eq!(parse(input).unwrap_err().to_string(),
+        "line 3, column 1: expected two location ids, found '7'");
+}
+
 }
@@ -1,21 +1,32 @@
-fn main() {
-    let input = include_str!("../input.txt");
-    let r = &parse(input);
-    println!("{}", solve1(r));
-    println!("{}", solve2(r));
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
+use std::fmt::Display;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Reports;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        solve1(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        solve2(input)
+    }
 }
 
 type Reports = Vec<Vec<i8>>;
 
-fn parse(input: &str) -> Reports {
+fn parse(input: &str) -> Result<Reports, ParseError> {
     input
         .lines()
-        .fold(vec![], |mut v, line| {
-            v.push(line.split_whitespace().map(|s|
-                s.parse().expect("nan")).collect::<Vec<i8>>()
-            );
-            v
-        })
+        .map(|line| line.split_whitespace().map(|s|
+            number(input, s)).collect::<Result<Vec<i8>, _>>()
+        )
+        .collect()
 }
 
 fn solve(r: Reports) -> impl Iterator<Item=Vec<i8>> {
@@ -64,9 +75,16 @@ use super::*;
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-    let r = &parse(input);
+    let r = &parse(input).unwrap();
     assert_eq!(solve1(r), 2);
     assert_eq!(solve2(r), 4);
 }
 
+    #[test]
+    fn test_parse_error() {
+        let input = "7 6 4 2 1
+1 2 7 8 900";
+    assert_eq!(parse(input), Err(ParseError::new(2, 9, "900", "number")));
+}
+
 }
@@ -0,0 +1,113 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
+use regex::Regex;
+use std::fmt::Display;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = String;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        p(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        pc(input)
+    }
+}
+
+type N = u32;
+
+const MUL: &str = r"mul\((\d+),(\d+)\)";
+
+/* the operands of every mul have to fit, so that p and pc need not check */
+fn parse(input: &str) -> Result<String, ParseError> {
+    let re = Regex::new(MUL).unwrap();
+    for (_,[x,y]) in re.captures_iter(input).map(|c| c.extract()) {
+        number::<N>(input, x)?;
+        number::<N>(input, y)?;
+    }
+    Ok(input.to_string())
+}
+
+pub fn p(input: &str) -> u64 {
+    let mut sum : u64 = 0;
+    let re = Regex::new(MUL).unwrap();
+    for (_,[x,y]) in re.captures_iter(input).map(|c| c.extract()) {
+        let ux = x.parse::<N>().expect("operand checked by parse") as u64;
+        let uy = y.parse::<N>().expect("operand checked by parse") as u64;
+        sum += ux * uy;
+    }
+    sum
+}
+
+pub fn pc(input: &str) -> u64 {
+    let re = Regex::new(&format!(r"{MUL}|(d)(o)\(\)|(d)o(n)'t\(\)")).unwrap();
+    let mut dont : bool = false;
+    let mut sum : u64 = 0;
+
+    for (_,[x,y]) in re.captures_iter(input).map(|c| c.extract()) {
+        match y {
+            "o" => { dont = false; },
+            "n" => { dont = true; },
+            _ => {
+                if dont { continue; }
+                let ux = x.parse::<N>().expect("operand checked by parse") as u64;
+                let uy = y.parse::<N>().expect("operand checked by parse") as u64;
+                sum += ux * uy;
+            },
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_p_simple() {
+        let input = "mul(2,4)".to_string();
+        assert_eq!(p(&input), 8);
+    }
+
+    #[test]
+    fn test_p_simple_filter() {
+        let input = "mul(5,5)-mul(32,64]".to_string();
+        assert_eq!(p(&input), 25);
+    }
+
+    #[test]
+    fn test_p_short_test() {
+        let input = "mul(2,4)+mul(5,5)mul(11,8)+mul(8,5)".to_string();
+        assert_eq!(p(&input), 161);
+    }
+
+    #[test]
+    fn test_p_test() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))".to_string();
+        assert_eq!(p(&input), 161);
+    }
+
+    #[test]
+    fn test_pc_test() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))".to_string();
+        assert_eq!(pc(&input), 48);
+    }
+
+    #[test]
+    fn test_long_operands() {
+        let input = "mul(1234,2)do()mul(4294967295,4294967295)".to_string();
+        assert_eq!(p(&Day03::parse(&input).unwrap()), 2468 + 18446744065119617025);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "mul(2,4)\nxmul(99999999999,2)".to_string();
+        assert_eq!(Day03::parse(&input).err(), Some(ParseError::new(2, 6, "99999999999", "number")));
+    }
+}
+
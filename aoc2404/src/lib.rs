@@ -1,13 +1,22 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::ParseError;
 use std::cmp;
-use std::fs;
-
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file {file_path}");
-    let c = count_xmas(&input);
-    let m = count_mas(&input);
-    println!("{c} / {m}");
+use std::fmt::Display;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = String;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        count_xmas(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        count_mas(input)
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -44,17 +53,17 @@ impl Xmas {
             _ => { return false; },
             }
         }
-        return true;
+        true
     }
 
     fn is_valid(&self, c: char, x: u32, y: u32) -> bool {
-        self.is_valid_p(c, P{x: x, y: y})
+        self.is_valid_p(c, P{x, y})
     }
 }
 
 fn prune_xmas(v: Vec<Xmas>, c: char, x: u32, y: u32) -> Vec<Xmas> {
     v.into_iter().filter(|xmas| {
-        let v = xmas.is_valid(c, u32::try_from(x).unwrap(), u32::try_from(y).unwrap());
+        let v = xmas.is_valid(c, x, y);
         // if !v { println!("remove {c},{x},{y}: {xmas:?}"); }
         v
     }).collect()
@@ -134,17 +143,17 @@ impl Mas {
             _ => { return false; },
             }
         }
-        return true;
+        true
     }
 
     fn is_valid(&self, c: char, x: u32, y: u32) -> bool {
-        self.is_valid_p(c, P{x: x, y: y})
+        self.is_valid_p(c, P{x, y})
     }
 }
 
 fn prune_mas(v: Vec<Mas>, c: char, x: u32, y: u32) -> Vec<Mas> {
     v.into_iter().filter(|xmas| {
-        let v = xmas.is_valid(c, u32::try_from(x).unwrap(), u32::try_from(y).unwrap());
+        let v = xmas.is_valid(c, x, y);
         // if !v { println!("remove {c},{x},{y}: {xmas:?}"); }
         v
     }).collect()
@@ -1,14 +1,22 @@
-use std::fs;
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
 use std::cmp;
+use std::fmt::Display;
 
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file {file_path}");
-    let mut man = parse(&input).unwrap();
-    let v = valid(&man);
-    let i = invalid(&mut man);
-    println!("{v} / {i}");
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Man;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        valid(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        invalid(&mut input.clone())
+    }
 }
 
 type Rules = Vec<[u32;2]>;
@@ -19,7 +27,8 @@ trait Pages {
     fn order(&mut self, rules: &Rules) -> &Self;
 }
 
-struct Man {
+#[derive(Clone)]
+pub struct Man {
     rules: Vec<[u32;2]>,
     pages: Vec<Vec<u32>>,
 }
@@ -78,7 +87,7 @@ impl Man {
     }
 }
 
-fn parse(input: &str) -> Option<Man> {
+fn parse(input: &str) -> Result<Man, ParseError> {
     let mut m = Man::new();
 
     for line in input.lines() {
@@ -90,8 +99,8 @@ fn parse(input: &str) -> Option<Man> {
             Some((x,y)) => {
                 // println!("= {x} / {y}");
                 m.rules.push([
-                    x.parse::<u32>().unwrap(),
-                    y.parse::<u32>().unwrap(),
+                    number(input, x)?,
+                    number(input, y)?,
                 ]);
             },
             _ => {
@@ -100,7 +109,7 @@ fn parse(input: &str) -> Option<Man> {
                     .split(',').filter(|s|!s.is_empty());
                 let mut v: Vec<u32> = vec![];
                 for s in st {
-                    v.push(s.parse::<u32>().unwrap());
+                    v.push(number(input, s)?);
                 }
 
                     // println!("{line} {v:?}");
@@ -110,7 +119,7 @@ fn parse(input: &str) -> Option<Man> {
             },
         }
     }
-    Some(m)
+    Ok(m)
 }
 
 fn valid(m: &Man) -> i64 {
@@ -187,4 +196,21 @@ fn test_orders() {
     assert_eq!(invalid(&mut m), 123, "invalid");
 }
 
+#[test]
+fn test_parse_error() {
+    let input = "
+47|53
+97|x3
+
+75,47,61
+";
+    assert_eq!(parse(input).err(), Some(ParseError::new(3, 4, "x3", "number")));
+    let input = "
+47|53
+
+75,47;61
+";
+    assert_eq!(parse(input).err(), Some(ParseError::new(4, 4, "47;61", "number")));
+}
+
 }
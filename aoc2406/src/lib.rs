@@ -0,0 +1,474 @@
+use aoc_common::day::Solution;
+use aoc_common::direction::Direction;
+use aoc_common::grid::{self, Grid, Pos};
+use aoc_common::parse::ParseError;
+use aoc_common::progress;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Lab;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        input.clone().run().map_or("loop".to_string(), |l| l.count().to_string())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        obstructions(input.clone())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Block {
+    c: char,
+}
+
+#[derive(Clone)]
+pub struct Lab {
+    map: Grid<Block>,
+    pos: Pos,
+    dir: Direction,
+}
+
+impl grid::Block for Block {
+    const EXPECTED: &'static str = "'.', '#' or guard";
+    fn empty() -> Block {
+        Block { c: '.', }
+    }
+    fn from_char(c: char) -> Option<Block> {
+        match c {
+            '.' | '#' | '^' | '>' | 'v' | '<' => Some(Block { c, }),
+            _ => None,
+        }
+    }
+    fn to_char(&self) -> char {
+        self.c
+    }
+}
+
+impl Block {
+    fn value(&self) -> isize {
+        match self.c {
+            '^' => 1,
+            '>' => 1,
+            'v' => 1,
+            '<' => 1,
+            'X' => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl Lab {
+    fn set_current(&mut self, c:char) {
+        self.set(self.pos.x, self.pos.y, c);
+    }
+    fn set(&mut self, x: isize, y: isize, c: char) {
+        self.map.set(Pos { x, y }, Block { c });
+    }
+    fn is_runnable(&self) -> bool {
+        self.map.contains(&self.pos)
+    }
+    fn step(&mut self) -> &Self {
+        if !self.is_runnable() {
+            return self;
+        }
+        let mut next = self.pos + self.dir;
+        let mut turns = 0;
+        while let Some(Block { c: '#' }) = self.map.get(&next) {
+            /* boxed in on all four sides, the guard stays put */
+            if turns == 4 { return self; }
+            turns += 1;
+            self.dir = self.dir.right();
+            next = self.pos + self.dir;
+        }
+        self.set_current('X');
+        self.pos = next;
+        if self.is_runnable() {
+            self.set_current(self.dir.to_char());
+        }
+        self
+    }
+    #[allow(dead_code)]
+    fn steps(&mut self, steps: isize) -> &Self {
+        for _n in 0..steps {
+            self.step();
+        }
+        self
+    }
+    fn run(&mut self) -> Option<&Self> {
+        let mut visited = HashSet::new();
+        while self.is_runnable() {
+            if !visited.insert((self.pos, self.dir)) {
+                return None;
+            }
+            self.step();
+        }
+        Some(self)
+    }
+    fn count(&self) -> isize {
+        self.map.cells().iter().fold(0, |s, b| s + b.value())
+    }
+    #[allow(dead_code)]
+    fn print(&self) -> &Self {
+        self.map.print();
+        self
+    }
+}
+
+fn parse(input: &str) -> Result<Lab, ParseError> {
+    let map: Grid<Block> = Grid::parse(input)?;
+    let (pos, dir) = map.iter()
+        .find_map(|(p, b)| Some((p, Direction::from_char(b.c)?)))
+        .ok_or(ParseError::end(input, "guard"))?;
+    Ok(Lab { map, pos, dir })
+}
+
+/*
+ * The guard only turns in front of obstacles, so the walk between two
+ * obstacles is precomputed as a jump for every cell and heading.
+ */
+struct Guard {
+    width: isize,
+    height: isize,
+    blocked: Vec<bool>,
+    /* the cell before the next obstacle ahead, None walks off the map */
+    jumps: Vec<[Option<Pos>; 4]>,
+    start: (Pos, Direction),
+}
+
+fn heading(d: Direction) -> usize {
+    d as usize / 2
+}
+
+impl Guard {
+    fn new(lab: &Lab) -> Self {
+        let map = &lab.map;
+        let blocked: Vec<bool> = map.cells().iter().map(|b| b.c == '#').collect();
+        let mut jumps = vec![[None; 4]; blocked.len()];
+        let cells: Vec<Pos> = map.iter().map(|(p, _)| p).collect();
+        for d in Direction::CARDINAL {
+            /* the cell ahead has to be done first */
+            let order: Box<dyn Iterator<Item = &Pos>> = match d {
+                Direction::North | Direction::West => Box::new(cells.iter()),
+                _ => Box::new(cells.iter().rev()),
+            };
+            for p in order {
+                let ahead = map.idx(&(*p + d));
+                jumps[map.idx(p).unwrap().0][heading(d)] = match ahead {
+                    None => None,
+                    Some(a) if blocked[a.0] => Some(*p),
+                    Some(a) => jumps[a.0][heading(d)],
+                };
+            }
+        }
+        Guard {
+            width: map.width,
+            height: map.height,
+            blocked,
+            jumps,
+            start: (lab.pos, lab.dir),
+        }
+    }
+    fn idx(&self, p: &Pos) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x >= self.width || p.y >= self.height {
+            return None;
+        }
+        Some((p.y * self.width + p.x) as usize)
+    }
+    /* (position, heading) bits of a whole map */
+    fn states(&self) -> Vec<u64> {
+        vec![0; (self.blocked.len() * 4).div_ceil(64)]
+    }
+    /*
+     * Cells in order of their first visit, each with the guard just before
+     * it. A guard that never leaves, looping or boxed in on all four
+     * sides, has none.
+     */
+    fn route(&self) -> Vec<(Pos, (Pos, Direction))> {
+        let mut seen = vec![false; self.blocked.len()];
+        let mut states = self.states();
+        let (mut p, mut d) = self.start;
+        seen[self.idx(&p).unwrap()] = true;
+        let mut route = vec![];
+        while let Some(a) = self.idx(&(p + d)) {
+            let k = self.idx(&p).unwrap() * 4 + heading(d);
+            if states[k / 64] & (1 << (k % 64)) != 0 {
+                return vec![];
+            }
+            states[k / 64] |= 1 << (k % 64);
+            if self.blocked[a] {
+                d = d.right();
+                continue;
+            }
+            if !seen[a] {
+                seen[a] = true;
+                route.push((p + d, (p, d)));
+            }
+            p = p + d;
+        }
+        route
+    }
+    /* does the guard loop with an extra obstacle at `o`, checked at turns only */
+    fn loops(&self, (mut p, mut d): (Pos, Direction), o: Pos, seen: &mut [u64]) -> bool {
+        seen.fill(0);
+        loop {
+            let mut stop = self.jumps[self.idx(&p).unwrap()][heading(d)];
+            let to_o = o - p;
+            if Pos::from(to_o.x.signum(), to_o.y.signum()) == d.pos()
+                && stop.is_none_or(|s| p.manhattan(&o) <= p.manhattan(&s)) {
+                stop = Some(o - d.pos());
+            }
+            let Some(s) = stop else { return false; };
+            p = s;
+            d = d.right();
+            let k = self.idx(&p).unwrap() * 4 + heading(d);
+            if seen[k / 64] & (1 << (k % 64)) != 0 {
+                return true;
+            }
+            seen[k / 64] |= 1 << (k % 64);
+        }
+    }
+}
+
+fn obstructions(g: Lab) -> usize {
+    loop_obstructions(&g).len()
+}
+
+/*
+ * Cells where one more obstacle traps the guard in a loop. Only cells of
+ * its route matter and never its start. Cancelling returns what was found.
+ */
+pub fn loop_obstructions(g: &Lab) -> Vec<Pos> {
+    let progress = progress::current();
+    let guard = Guard::new(g);
+    let route = guard.route();
+    let mut seen = guard.states();
+    let mut found = vec![];
+    for (i, (o, before)) in route.iter().enumerate() {
+        progress.report("obstructions", i, Some(route.len()));
+        if progress.cancelled() { break; }
+        if guard.loops(*before, *o, &mut seen) { found.push(*o) }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+use super::*;
+
+#[test]
+fn test_turnturn() {
+    let input = "
+....#....
+.#..^#...
+........#
+#........
+";
+    let mut g = parse(input).unwrap();
+    assert_eq!(g.map.width, 9);
+    assert_eq!(g.map.height, 4);
+    assert_eq!(g.print().count(), 1);
+    assert_eq!(g.run().unwrap().print().count(), 3);
+}
+
+#[test]
+fn test_run() {
+    let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let mut g = parse(input).unwrap();
+    assert_eq!(g.map.width, 10);
+    assert_eq!(g.map.height, 10);
+    assert_eq!(g.print().count(), 1);
+    assert_eq!(g.step().print().count(), 2);
+    assert_eq!(g.steps(4).print().count(), 6);
+    assert_eq!(g.steps(4).print().count(), 10);
+    assert_eq!(g.steps(5).print().count(), 15);
+    assert_eq!(g.steps(6).print().count(), 20);
+    assert_eq!(g.run().unwrap().print().count(), 41);
+}
+
+#[test]
+fn test_obstructions() {
+    let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let g = parse(input).unwrap();
+    assert_eq!(g.map.width, 10);
+    assert_eq!(g.map.height, 10);
+
+    assert_eq!(obstructions(g), 6);
+}
+
+#[test]
+fn test_guard() {
+    let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let g = parse(input).unwrap();
+    let guard = Guard::new(&g);
+    let route = guard.route();
+    assert_eq!(route.len() + 1, 41);
+    let mut seen = guard.states();
+    for (o, before) in route {
+        let mut g2 = g.clone();
+        g2.set(o.x, o.y, '#');
+        assert_eq!(guard.loops(before, o, &mut seen), g2.run().is_none(), "obstruction at {o}");
+    }
+}
+
+#[test]
+fn test_obstruction_list() {
+    let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let g = parse(input).unwrap();
+    let mut found = loop_obstructions(&g);
+    found.sort_by_key(|p| (p.y, p.x));
+    assert_eq!(found, vec![
+        Pos { x: 3, y: 6 },
+        Pos { x: 6, y: 7 },
+        Pos { x: 7, y: 7 },
+        Pos { x: 1, y: 8 },
+        Pos { x: 3, y: 8 },
+        Pos { x: 7, y: 9 },
+    ]);
+}
+
+/* every free cell but the start, by brute force */
+fn brute_force(g: &Lab) -> Vec<Pos> {
+    let mut found = vec![];
+    for y in 0..g.map.height {
+        for x in 0..g.map.width {
+            let o = Pos { x, y };
+            if o == g.pos || g.map.get(&o).unwrap().c == '#' { continue; }
+            let mut g2 = g.clone();
+            g2.set(x, y, '#');
+            if g2.run().is_none() { found.push(o) }
+        }
+    }
+    found
+}
+
+#[test]
+fn test_rectangular() {
+    let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let wide: String = input.lines().map(|l| if l.is_empty() { "\n".to_string() } else { format!("{l}....\n") }).collect();
+    let tall = format!("{input}..........\n.#........\n..........\n");
+    for input in [wide, tall] {
+        let g = parse(&input).unwrap();
+        assert_ne!(g.map.width, g.map.height);
+        let mut found = loop_obstructions(&g);
+        found.sort_by_key(|p| (p.y, p.x));
+        assert!(!found.is_empty());
+        assert!(!found.contains(&g.pos));
+        assert_eq!(found, brute_force(&g));
+    }
+}
+
+#[test]
+fn test_boxed_in() {
+    let input = "
+.#.
+#^#
+.#.
+";
+    let g = parse(input).unwrap();
+    assert!(Guard::new(&g).route().is_empty());
+    assert!(loop_obstructions(&g).is_empty());
+    assert_eq!(Day06::part1(&g).to_string(), "loop");
+    assert_eq!(Day06::part2(&g).to_string(), "0");
+    /* turning three times still gets out */
+    let input = "
+.#.
+#^#
+...
+";
+    let g = parse(input).unwrap();
+    assert_eq!(Guard::new(&g).route().len(), 1);
+    assert_eq!(Day06::part1(&g).to_string(), "2");
+}
+
+#[test]
+fn test_looping() {
+    let input = "
+.#..
+.^.#
+#...
+..#.
+";
+    let g = parse(input).unwrap();
+    assert!(g.clone().run().is_none());
+    assert!(Guard::new(&g).route().is_empty());
+    assert_eq!(Day06::part1(&g).to_string(), "loop");
+    assert_eq!(Day06::part2(&g).to_string(), "0");
+}
+
+#[test]
+fn test_parse_error() {
+    let input = "
+....#
+.#..^
+..o..
+";
+    assert_eq!(parse(input).err(), Some(ParseError::new(4, 3, "o", "'.', '#' or guard")));
+    let input = "
+....#
+.#...
+";
+    assert_eq!(parse(input).err(), Some(ParseError::new(4, 1, "", "guard")));
+}
+
+}
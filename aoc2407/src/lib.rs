@@ -1,10 +1,22 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
 use partial_application::partial;
-
-fn main() {
-    let input = include_str!("../input.txt");
-    let p = &parse(input);
-    println!("{}", solve1(p));
-    println!("{}", solve2(p));
+use std::fmt::Display;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Calcs;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        solve1(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        solve2(input)
+    }
 }
 
 type Base = u64;
@@ -36,19 +48,26 @@ fn concat(a: &Base, b: &Base) -> Base {
     (a.to_string() + &b.to_string()).parse::<Base>().expect("nan")
 }
 
-fn parse(input: &str) -> Calcs {
+fn parse(input: &str) -> Result<Calcs, ParseError> {
     input
         .lines()
         .filter(|l| !l.is_empty())
-        .fold(vec![], |mut v, line| {
-            v.push(line.split_whitespace().map(|s|
-                s.trim_end_matches(':').parse().expect("nan")).collect::<Vec<Base>>()
-            );
-            v
+        .map(|line| {
+            let (target, values) = line.split_once(':')
+                .ok_or(ParseError::at(input, line, "'<target>: <values>'"))?;
+            let mut calc = vec![number(input, target)?];
+            for s in values.split_whitespace() {
+                calc.push(number(input, s)?);
+            }
+            if calc.len() < 2 {
+                return Err(ParseError::at(input, &values[values.len()..], "number"));
+            }
+            Ok(calc)
         })
+        .collect()
 }
 
-fn is_solvable(operations: &[Operation], v: &Vec<Base>) -> bool {
+fn is_solvable(operations: &[Operation], v: &[Base]) -> bool {
     let mut iter = v.iter();
     let target = *iter.next().expect("no values");
     let first = *iter.next().expect("too few elements");
@@ -56,7 +75,7 @@ fn is_solvable(operations: &[Operation], v: &Vec<Base>) -> bool {
         let mut new_state = Vec::with_capacity(state.len() * operations.len());
         for val in state {
             for f in operations {
-                let new_val = f(&val, &c);
+                let new_val = f(&val, c);
                 if new_val <= target {
                     new_state.push(new_val);
                 }
@@ -90,7 +109,7 @@ use super::*;
         let input = "
 190: 1 90
 ";
-    let r = &parse(input);
+    let r = &parse(input).unwrap();
     assert_eq!(solve2(r), 190);
 }
 
@@ -107,9 +126,26 @@ use super::*;
 21037: 9 7 18 13
 292: 11 6 16 20
 ";
-    let r = &parse(input);
+    let r = &parse(input).unwrap();
     assert_eq!(solve1(r), 3749);
     assert_eq!(solve2(r), 11387);
 }
 
+    #[test]
+    fn test_parse_error() {
+        let input = "
+190: 10 19
+3267 81 40 27
+";
+    assert_eq!(parse(input), Err(ParseError::new(3, 1, "3267 81 40 27", "'<target>: <values>'")));
+        let input = "
+190: 10 1x9
+";
+    assert_eq!(parse(input), Err(ParseError::new(2, 9, "1x9", "number")));
+        let input = "
+190:
+";
+    assert_eq!(parse(input), Err(ParseError::new(2, 5, "", "number")));
+}
+
 }
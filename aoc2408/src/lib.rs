@@ -0,0 +1,210 @@
+use aoc_common::day::Solution;
+use aoc_common::grid::{self, Grid, Pos};
+use aoc_common::parse::ParseError;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = City;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        City::parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        antinodes(input.clone())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        more_antinodes(input.clone())
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+struct Block {
+    c: char,
+}
+
+impl grid::Block for Block {
+    fn empty() -> Block {
+        Block { c: '.', }
+    }
+    fn from_char(c: char) -> Option<Block> {
+        Some(Block { c, })
+    }
+    fn to_char(&self) -> char {
+        self.c
+    }
+}
+
+impl std::fmt::Display for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "'{}'", self.c)
+    }
+}
+
+#[derive(Clone)]
+pub struct City {
+    grid: Grid<Block>,
+    sigs: HashMap<Block, Vec<Pos>>,
+}
+
+impl City {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid: Grid<Block> = Grid::parse(input)?;
+        let mut sigs: HashMap<Block, Vec<Pos>> = HashMap::new();
+        for (pos, b) in grid.iter() {
+            match b.c {
+                '.' => {},
+                '#' => {},
+                _ => {
+                    sigs.entry(b).or_default().push(pos);
+                },
+            }
+        }
+        Ok(City { grid, sigs })
+    }
+}
+
+fn antinodes(g: City) -> usize {
+    let mut nodes: HashSet<Pos> = HashSet::new();
+    for pos in g.sigs.values() {
+        let p = pos.len();
+        for n in 0..p {
+            for m in 0..n {
+                let (npos, mpos) = (pos[n], pos[m]);
+                for (p, _) in g.grid.iter() {
+                    if p == npos || p == mpos { continue; }
+                    if !Pos::is_line(npos, mpos, p) { continue; }
+                    // println!("-- n={n},m={m},p={p}");
+                    let d1 = Grid::<Block>::dist(p, npos);
+                    let d2 = Grid::<Block>::dist(p, mpos);
+
+                    if d1 == 2*d2 || d2 == 2*d1 {
+                        nodes.insert(p);
+                    }
+                }
+            }
+        }
+    }
+    nodes.len()
+}
+
+fn more_antinodes(g: City) -> usize {
+    let mut nodes: HashSet<Pos> = HashSet::new();
+    for pos in g.sigs.values() {
+        let len = pos.len();
+        if len > 1 {
+            for p in pos {
+                nodes.insert(*p);
+            }
+        }
+        for n in 0..len {
+            for m in 0..n {
+                let (npos, mpos) = (pos[n], pos[m]);
+                for (p, _) in g.grid.iter() {
+                    if !Pos::is_line(npos, mpos, p) { continue; }
+                    nodes.insert(p);
+                }
+            }
+        }
+    }
+    nodes.len()
+}
+
+#[cfg(test)]
+mod tests {
+use super::*;
+
+#[test]
+fn test_small() {
+    let input = "
+.a.......
+.a.......
+......bb.
+.........
+";
+    let g = City::parse(input).unwrap();
+    assert_eq!(g.grid.width, 9);
+    assert_eq!(g.grid.height, 4);
+
+    assert_eq!(antinodes(g), 3);
+}
+
+#[test]
+fn test_mid() {
+    let input = "
+..........
+...#......
+..........
+....a.....
+..........
+.....a....
+..........
+......#...
+..........
+..........
+";
+    let g = City::parse(input).unwrap();
+    assert_eq!(g.grid.width, 10);
+    assert_eq!(g.grid.height, 10);
+
+    assert_eq!(antinodes(g), 2);
+}
+
+#[test]
+fn test_run() {
+    let input = "
+##....#....#
+.#.#....0...
+..#.#0....#.
+..##...0....
+....0....#..
+.#...#A....#
+...#..#.....
+#....#.#....
+..#.....A...
+....#....A..
+.#........#.
+...#......##
+";
+    let g = City::parse(input).unwrap();
+    assert_eq!(g.grid.width, 12);
+    assert_eq!(g.grid.height, 12);
+
+    assert_eq!(antinodes(g.clone()), 14);
+    assert_eq!(more_antinodes(g), 34);
+}
+
+#[test]
+fn test_run2() {
+    let input = "
+T....#....
+...T......
+.T....#...
+.........#
+..#.......
+..........
+...#......
+..........
+....#.....
+..........
+";
+let g = City::parse(input).unwrap();
+assert_eq!(g.grid.width, 10);
+assert_eq!(g.grid.height, 10);
+
+assert_eq!(more_antinodes(g), 9);
+}
+
+#[test]
+fn test_parse_error() {
+    let input = "
+..........
+...#.....
+";
+    assert_eq!(City::parse(input).err(), Some(ParseError::new(3, 1, "...#.....", "row of width 10")));
+}
+
+}
@@ -1,14 +1,22 @@
-use std::fs;
 use Block::*;
+use aoc_common::day::Solution;
+use aoc_common::parse::ParseError;
+use std::fmt::Display;
 
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file {file_path}");
-    let o = Obj::parse(&input).unwrap();
-    let r1 = defrag(o.clone()).checksum();
-    let r2 = defrag2(o.clone()).checksum();
-    println!("{r1} / {r2}");
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Obj;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Obj::parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        defrag(input.clone()).checksum()
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        defrag2(input.clone()).checksum()
+    }
 }
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
@@ -58,7 +66,7 @@ impl std::fmt::Display for File {
 }
 
 #[derive(Clone, Hash, PartialEq, Eq)]
-struct Obj {
+pub struct Obj {
     files: Vec<File>,
     len: usize,
 }
@@ -90,28 +98,29 @@ impl std::fmt::Display for Obj {
 }
 
 impl Obj {
-    fn parse(input: &str) -> Option<Obj> {
+    fn parse(input: &str) -> Result<Obj, ParseError> {
         let mut files = vec![];
         let mut id: usize = 0;
         let mut pos: usize = 0;
-        let mut chars = input.chars();
-        while let Some(c) = chars.next() {
-            if !c.is_ascii_digit() { continue; }
-            let len = usize::try_from(c.to_digit(10)?).ok()?;
-            let c2 = chars.next();
+        let mut digits = input.char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| c.to_digit(10)
+                .map(|d| d as usize)
+                .ok_or(ParseError::at(input, &input[i..i + c.len_utf8()], "digit")));
+        while let Some(len) = digits.next() {
+            let len = len?;
             files.push(File { id, pos, len });
             // println!("{c} => f{id}:{pos}..={}", pos + len - 1);
             pos += len;
             id += 1;
-            if c2 != None {
-                if !c2?.is_ascii_digit() { continue; }
-                let gap_len = usize::try_from(c2?.to_digit(10)?).ok()?;
-                pos += gap_len;
+            if let Some(gap_len) = digits.next() {
+                pos += gap_len?;
             }
         }
-        let last = files.iter().last()?;
+        let last = files.iter().last()
+            .ok_or(ParseError::end(input, "digit"))?;
         let len = last.pos + last.len;
-        Some(Obj{ files, len, })
+        Ok(Obj{ files, len, })
     }
     fn blocks(&self) -> Vec<Block> {
         let mut blocks = self.files.iter().fold(
@@ -233,4 +242,13 @@ fn test_mid() {
     assert_eq!(defrag2(o.clone()).checksum(), 2858);
 }
 
+#[test]
+fn test_parse_error() {
+    let input = "
+2333133121x14131402
+";
+    assert_eq!(Obj::parse(input).err(), Some(ParseError::new(2, 11, "x", "digit")));
+    assert_eq!(Obj::parse("\n").err(), Some(ParseError::new(2, 1, "", "digit")));
+}
+
 }
@@ -0,0 +1,131 @@
+use aoc_common::day::Solution;
+use aoc_common::grid::{
+    Grid,
+    Pos,
+};
+use aoc_common::parse::ParseError;
+use aoc_common::search::{bfs, Paths};
+use std::fmt::Display;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Grid;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        paths(input)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        dpaths(input)
+    }
+}
+
+fn trails(g: &Grid, p0: Pos) -> Paths<Pos, usize> {
+    bfs([p0], |p| {
+        let next = char::from_u32(g.get(p).unwrap() as u32 + 1).unwrap();
+        g.neighbors_xy(p).into_iter()
+            .filter(move |(_, c)| *c == next)
+            .map(|(p, _)| p)
+    }, |_| false)
+}
+
+fn tops(g: &Grid) -> impl Iterator<Item = (Paths<Pos, usize>, Vec<Pos>)> + '_ {
+    g.iter().filter(|(_, c)| *c == '0').map(|(p0, _)| {
+        let t = trails(g, p0);
+        let ends = t.cost.keys().filter(|p| g.get(p) == Some('9')).copied().collect();
+        (t, ends)
+    })
+}
+
+fn paths(g: &Grid) -> usize {
+    tops(g).map(|(_, ends)| ends.len()).sum()
+}
+
+fn dpaths(g: &Grid) -> usize {
+    tops(g).map(|(t, ends)| ends.into_iter().map(|e| t.count(e)).sum::<usize>()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+use super::*;
+
+#[test]
+fn test_smaller() {
+    let input = "
+...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9
+";
+    let g = Grid::parse(input).unwrap();
+    assert_eq!(paths(&g), 2);
+}
+
+#[test]
+fn test_smaller_d() {
+    let input = "
+..90..9
+...1.98
+...2..7
+6543456
+765.987
+876....
+987....
+";
+    let g = Grid::parse(input).unwrap();
+    assert_eq!(dpaths(&g), 13);
+}
+
+#[test]
+fn test_small() {
+    let input = "
+10..9..
+2...8..
+3...7..
+4567654
+...8..3
+...9..2
+.....01
+";
+    let g = Grid::parse(input).unwrap();
+    assert_eq!(paths(&g), 3);
+}
+
+#[test]
+fn test_small_d() {
+    let input = "
+012345
+123456
+234567
+345678
+4.6789
+56789.
+";
+    let g = Grid::parse(input).unwrap();
+    assert_eq!(dpaths(&g), 227);
+}
+
+#[test]
+fn test_mid() {
+    let input = "
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+    let g = Grid::parse(input).unwrap();
+    assert_eq!(paths(&g), 36);
+    assert_eq!(dpaths(&g), 81);
+}
+
+}
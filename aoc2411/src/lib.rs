@@ -1,30 +1,37 @@
-use std::fs;
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
 use std::collections::HashMap;
+use std::fmt::Display;
 
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file {file_path}");
-    let mut o = parse(&input).unwrap();
-    let r1 = blink(25, &mut o);
-    println!("r1: {r1}");
-    let r2 = blink(50, &mut o);
-    println!("r2: {r2}");
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Stones;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        blink(25, &mut input.clone())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        blink(75, &mut input.clone())
+    }
 }
 
 type N = u64;
 type Stone = u64;
 type Stones = HashMap<Stone, N>;
 
-fn parse(input: &str) -> Option<Stones> {
-    Some(input.lines()
+fn parse(input: &str) -> Result<Stones, ParseError> {
+    input.lines()
         .filter(|l| !l.is_empty())
-        .fold(Stones::new(), |o, l| l.split_whitespace()
-            .fold(o, |mut o, s| {
-                let s = s.parse().unwrap();
-                o.entry(s).and_modify(|n| *n += 1).or_insert(1);
-                o
-            })))
+        .flat_map(|l| l.split_whitespace())
+        .try_fold(Stones::new(), |mut o, s| {
+            let s = number(input, s)?;
+            o.entry(s).and_modify(|n| *n += 1).or_insert(1);
+            Ok(o)
+        })
 }
 
 fn sum(stones: &Stones) -> usize {
@@ -42,7 +49,7 @@ fn blink1(stones: &Stones) -> Stones {
     // println!("next round:");
     let mut new_stones = Stones::with_capacity(stones.len() * 2);
     for (stone, n) in stones {
-        blink_rules(&stone).into_iter().for_each(|s| {
+        blink_rules(stone).into_iter().for_each(|s| {
             new_stones.entry(s).and_modify(|m| *m += n).or_insert(*n);
         });
     }
@@ -53,7 +60,7 @@ fn blink_rules(s: &Stone) -> Vec<Stone> {
     let mut ret = Vec::with_capacity(2);
     let digits = format!("{}", s).len();
     match *s {
-        val if val == 0 => ret.push(1),
+        0 => ret.push(1),
         val if digits % 2 == 0 => {
             ret.push(val / 10_u64.pow(digits as u32/2));
             ret.push(val % 10_u64.pow(digits as u32/2));
@@ -133,4 +140,10 @@ fn test_1() {
     assert_eq!(blink(25, &mut o), 231278);
 }
 
+#[test]
+fn test_parse_error() {
+    let input = "125 17 -1";
+    assert_eq!(parse(input), Err(ParseError::new(1, 8, "-1", "number")));
+}
+
 }
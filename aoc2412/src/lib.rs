@@ -0,0 +1,156 @@
+use aoc_common::day::Solution;
+use aoc_common::grid::{
+    self,
+    Connectivity,
+    Grid,
+    Region,
+};
+use aoc_common::parse::ParseError;
+use std::fmt::Display;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Grid<Plant>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        price(input, |r| r.perimeter())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        price(input, |r| r.sides())
+    }
+}
+
+#[derive(Clone,Copy,Hash,Debug,PartialEq,Eq)]
+pub struct Plant(char);
+
+impl grid::Block for Plant {
+    const EXPECTED: &'static str = "plant letter";
+    fn empty() -> Plant {
+        Plant('.')
+    }
+    fn from_char(c: char) -> Option<Plant> {
+        c.is_ascii_uppercase().then_some(Plant(c))
+    }
+    fn to_char(&self) -> char {
+        self.0
+    }
+}
+
+fn parse(input: &str) -> Result<Grid<Plant>, ParseError> {
+    Grid::parse(input)
+}
+
+/* fence price: area times perimeter, or area times sides in bulk */
+fn price(g: &Grid<Plant>, fence: impl Fn(&Region<Plant>) -> usize) -> usize {
+    g.regions(Connectivity::Four).iter()
+        .map(|r| r.area() * fence(r))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(input: &str) -> (usize, usize) {
+        let g = parse(input).unwrap();
+        (price(&g, |r| r.perimeter()), price(&g, |r| r.sides()))
+    }
+
+    #[test]
+    fn test_small() {
+        let input = "
+AAAA
+BBCD
+BBCC
+EEEC
+";
+        let g = parse(input).unwrap();
+        let r = g.regions(Connectivity::Four);
+        assert_eq!(r.iter().map(|r| (r.cell.0, r.area(), r.perimeter(), r.sides())).collect::<Vec<_>>(), vec![
+            ('A', 4, 10, 4),
+            ('B', 4, 8, 4),
+            ('C', 4, 10, 8),
+            ('D', 1, 4, 4),
+            ('E', 3, 8, 4),
+        ]);
+        assert_eq!(prices(input), (140, 80));
+    }
+
+    #[test]
+    fn test_nested() {
+        let input = "
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO
+";
+        assert_eq!(prices(input), (772, 436));
+    }
+
+    #[test]
+    fn test_e_shape() {
+        let input = "
+EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE
+";
+        assert_eq!(prices(input).1, 236);
+    }
+
+    #[test]
+    fn test_inside_touching() {
+        let input = "
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+        assert_eq!(prices(input).1, 368);
+    }
+
+    #[test]
+    fn test_larger() {
+        let input = "
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+        assert_eq!(prices(input), (1930, 1206));
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = "\r\nAAAA\r\nBBCD\r\nBBCC\r\nEEEC\r\n";
+        assert_eq!(prices(input), (140, 80));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+AAAA
+BBcD
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 3, "c", "plant letter")));
+        let input = "
+AAAA
+BBC
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 1, "BBC", "row of width 4")));
+    }
+}
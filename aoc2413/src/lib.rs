@@ -1,23 +1,29 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
 use regex::Regex;
 use std::cmp::min;
-use std::fs;
+use std::fmt::Display;
 use std::ops;
 
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file");
-    let i = &parse(&input);
-    let r1 = win_sum(i.clone());
-    let i2: Vec<G> = i.clone().into_iter().map(|mut g|{
-        g.goal = g.goal + Pos {x:10000000000000, y:10000000000000}; g
-    }).collect();
-    // println!("{:?}", &i2);
-    let r2 = win_sum2(i2.clone());
-    println!("{r1} / {r2}");
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<G>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        win_sum(input.clone())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        let i2: Vec<G> = input.clone().into_iter().map(|mut g|{
+            g.goal = g.goal + Pos {x:10000000000000, y:10000000000000}; g
+        }).collect();
+        win_sum2(i2)
+    }
 }
 
-const MAX_CLICKS: Size = 100;
 type Size = i64;
 
 #[derive(Clone,Copy,Hash,PartialEq,Debug)]
@@ -53,7 +59,7 @@ impl S {
 }
 
 #[derive(Clone,Hash,Debug)]
-struct G/*ame*/ {
+pub struct G/*ame*/ {
     a: B,
     b: B,
     goal: Pos,
@@ -64,26 +70,37 @@ struct G/*ame*/ {
  * Button B: X+22, Y+67
  * Prize: X=8400, Y=5400
  */
-fn parse(input: &String) -> Vec<G> {
-    let re = Regex::new(r"(?ms)^Button A: X\+(\d+), Y\+(\d+)$
-^Button B: X\+(\d+), Y\+(\d+)$
-^Prize: X=(\d+), Y=(\d+)$
-").unwrap();
-    re.captures_iter(input)
-        // .inspect(|c|println!("{:?}, {:?}", c, 0))
-        .map(|c| c.extract())
-        .map(|(_,c)| c.map(|c|c.parse::<Size>().unwrap()))
-        .map(|[ax,ay,bx,by,gx,gy]| {
+fn parse(input: &str) -> Result<Vec<G>, ParseError> {
+    let lines = [
+        (Regex::new(r"^Button A: X\+(\d+), Y\+(\d+)$").unwrap(), "'Button A: X+<x>, Y+<y>'"),
+        (Regex::new(r"^Button B: X\+(\d+), Y\+(\d+)$").unwrap(), "'Button B: X+<x>, Y+<y>'"),
+        (Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").unwrap(), "'Prize: X=<x>, Y=<y>'"),
+    ];
+    let mut games = vec![];
+    let mut v: Vec<Size> = Vec::with_capacity(6);
+    for line in input.lines().filter(|l| !l.is_empty()) {
+        let (re, expected) = &lines[v.len() / 2];
+        let (_, [x, y]) = re.captures(line)
+            .ok_or(ParseError::at(input, line, *expected))?
+            .extract();
+        v.push(number(input, x)?);
+        v.push(number(input, y)?);
+        if let [ax,ay,bx,by,gx,gy] = v[..] {
             let a = Pos{x:ax, y:ay};
             let b = Pos{x:bx, y:by};
 
-            G {
+            games.push(G {
                 a: B{b:'A',pos:a,price:3},
                 b: B{b:'B',pos:b,price:1},
                 goal:Pos{x:gx, y:gy},
-            }
-        })
-        .collect()
+            });
+            v.clear();
+        }
+    }
+    if !v.is_empty() {
+        return Err(ParseError::end(input, lines[v.len() / 2].1));
+    }
+    Ok(games)
 }
 
 fn win_sum(g: impl IntoIterator<Item=G>) -> Size {
@@ -125,8 +142,8 @@ fn solve(g: G) -> Option<S> {
             }
         }
     }
-    hits.sort_by(|a,b|a.price().cmp(&b.price()));
-    if hits.len() > 0 {
+    hits.sort_by_key(|a| a.price());
+    if !hits.is_empty() {
         return Some(hits[0].clone());
     }
     None
@@ -159,8 +176,8 @@ mod tests {
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 280);
-        assert_eq!(win_sum2(parse(&input)), 280);
+        assert_eq!(win_sum(parse(&input).unwrap()), 280);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 280);
     }
 
     #[test]
@@ -170,8 +187,8 @@ Button A: X+26, Y+66
 Button B: X+67, Y+21
 Prize: X=12748, Y=12176
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 0);
-        assert_eq!(win_sum2(parse(&input)), 0);
+        assert_eq!(win_sum(parse(&input).unwrap()), 0);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 0);
     }
 
     #[test]
@@ -181,8 +198,8 @@ Button A: X+17, Y+86
 Button B: X+84, Y+37
 Prize: X=7870, Y=6450
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 200);
-        assert_eq!(win_sum2(parse(&input)), 200);
+        assert_eq!(win_sum(parse(&input).unwrap()), 200);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 200);
     }
 
     #[test]
@@ -192,8 +209,8 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 0);
-        assert_eq!(win_sum2(parse(&input)), 0);
+        assert_eq!(win_sum(parse(&input).unwrap()), 0);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 0);
     }
 
     #[test]
@@ -215,8 +232,8 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 480);
-        assert_eq!(win_sum2(parse(&input)), 480);
+        assert_eq!(win_sum(parse(&input).unwrap()), 480);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 480);
     }
 
     #[test]
@@ -230,8 +247,8 @@ Button A: X+91, Y+12
 Button B: X+13, Y+81
 Prize: X=1339, Y=5568
 ".to_string();
-        assert_eq!(win_sum(parse(&input)), 315);
-        assert_eq!(win_sum2(parse(&input)), 315);
+        assert_eq!(win_sum(parse(&input).unwrap()), 315);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 315);
     }
 
     #[test]
@@ -253,7 +270,28 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=10000000018641, Y=10000000010279
 ".to_string();
-        assert_eq!(win_sum2(parse(&input)), 875318608908);
+        assert_eq!(win_sum2(parse(&input).unwrap()), 875318608908);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+Button A: X+94, Y+34
+Button B: X+22, Y=67
+Prize: X=8400, Y=5400
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(3, 1, "Button B: X+22, Y=67", "'Button B: X+<x>, Y+<y>'")));
+        let input = "
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 1, "", "'Prize: X=<x>, Y=<y>'")));
+        let input = "
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=99999999999999999999, Y=5400
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 10, "99999999999999999999", "number")));
     }
 
 }
@@ -0,0 +1,446 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
+use aoc_common::grid::{Area, Block, Connectivity, Grid, Pos};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Setup;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Setup::parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut r = input.room();
+        r.run(input.steps);
+        r.safety().map_or("no quadrants".to_string(), |s| s.to_string())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        let r = input.room();
+        r.tree().map_or("none".to_string(), |t| t.round.to_string())
+    }
+}
+
+#[cfg(test)]
+fn parse(input: &str) -> Result<Vec<Dude>, ParseError> {
+    Ok(Setup::parse(input)?.dudes)
+}
+
+/*
+ * room=11x7 steps=100
+ * p=0,4 v=3,-3
+ *
+ * The header line is optional, so are both of its settings. Without a
+ * room size, robots inside 11x7 are the example, anything else is the
+ * 101x103 puzzle room.
+ */
+#[derive(Clone,Debug)]
+pub struct Setup {
+    width: isize,
+    height: isize,
+    steps: usize,
+    dudes: Vec<Dude>,
+}
+
+impl Setup {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let re = Regex::new(r"^p=(\d+),(\d+) v=([\d-]+),([\d-]+)$").unwrap();
+        let mut lines = input.lines().filter(|l| !l.is_empty()).peekable();
+        let (mut size, mut steps) = (None, 100);
+        if let Some(header) = lines.next_if(|l| !l.starts_with("p=")) {
+            for token in header.split_whitespace() {
+                match token.split_once('=') {
+                    Some(("room", v)) => {
+                        let (w, h) = v.split_once('x')
+                            .ok_or(ParseError::at(input, v, "'<width>x<height>'"))?;
+                        let (w, h): (isize, isize) = (number(input, w)?, number(input, h)?);
+                        if w <= 0 || h <= 0 {
+                            return Err(ParseError::at(input, v, "positive '<width>x<height>'"));
+                        }
+                        size = Some((w, h));
+                    },
+                    Some(("steps", v)) => steps = number(input, v)?,
+                    _ => return Err(ParseError::at(input, token, "'room=<width>x<height>' or 'steps=<n>'")),
+                }
+            }
+        }
+        let dudes = lines
+            .map(|l| re.captures(l)
+                .ok_or(ParseError::at(input, l, "'p=<x>,<y> v=<dx>,<dy>'")))
+            // .inspect(|c|println!("read {:?}", c))
+            .map(|c| {
+                let (_, [x,y,vx,vy]) = c?.extract();
+                Ok(Dude {
+                    pos: Pos { x: number(input, x)?, y: number(input, y)? },
+                    v: Pos { x: number(input, vx)?, y: number(input, vy)? },
+                })
+            })
+            .collect::<Result<Vec<Dude>, ParseError>>()?;
+        let (width, height) = size.unwrap_or_else(|| Self::detect(&dudes));
+        Ok(Setup { width, height, steps, dudes })
+    }
+    fn detect(dudes: &[Dude]) -> (isize, isize) {
+        let example = Area::from(Pos { x: 0, y: 0 }, Pos { x: 10, y: 6 });
+        match dudes.iter().all(|d| example.contains(d.pos)) {
+            true => (11, 7),
+            false => (101, 103),
+        }
+    }
+    pub fn room(&self) -> Room {
+        Room {
+            grid: Grid::toroidal(self.width, self.height),
+            dudes: self.dudes.clone(),
+            round: 0,
+        }
+    }
+}
+
+#[derive(Clone,Hash,Debug)]
+pub struct Dude {
+    pos: Pos,
+    v: Pos,
+}
+
+#[derive(Clone,Hash,Debug)]
+pub struct Room {
+    grid: Grid,
+    dudes: Vec<Dude>,
+    round: usize,
+}
+
+impl Room {
+    /* robots on the middle row or column count for no quadrant */
+    fn safety(self) -> Option<usize> {
+        if self.grid.width % 2 == 0 || self.grid.height % 2 == 0 {
+            return None;
+        }
+        let areas = [
+            Area {
+                from: Pos{x: 0, y: 0},
+                to: Pos{x: self.grid.width / 2 - 1, y: self.grid.height / 2 - 1},
+            },
+            Area {
+                from: Pos{x: self.grid.width / 2 + 1, y: 0},
+                to: Pos{x: self.grid.width, y: self.grid.height / 2 - 1},
+            },
+            Area {
+                from: Pos{x: 0, y: self.grid.height / 2 + 1},
+                to: Pos{x: self.grid.width / 2 - 1, y: self.grid.height},
+            },
+            Area {
+                from: Pos{x: self.grid.width / 2 + 1, y: self.grid.height / 2 + 1},
+                to: Pos{x: self.grid.width, y: self.grid.height},
+            },
+        ];
+        let mut n = [0,0,0,0];
+        // println!("dudes: {:?}", self.dudes);
+        // println!("areas {:?}", areas);
+        for dude in self.dudes {
+            // println!("dude: {:?} {:?}", dude.pos, areas[2].contains(dude.pos));
+            for i in 0..4 {
+                if areas[i].contains(dude.pos) {
+                    n[i] += 1;
+                    break;
+                }
+            }
+        }
+        Some(n.iter()
+            // .inspect(|n| print!("{} ", n))
+            .product())
+    }
+    fn run(&mut self, steps: usize) {
+        *self = self.at(steps);
+    }
+    /* positions `steps` seconds later, each axis only matters modulo its size */
+    pub fn at(&self, steps: usize) -> Room {
+        let (w, h) = (self.grid.width, self.grid.height);
+        let (sx, sy) = ((steps % w as usize) as isize, (steps % h as usize) as isize);
+        let dudes = self.dudes.iter().map(|dude| Dude {
+            pos: self.grid.wrap(dude.pos + Pos { x: dude.v.x * sx, y: dude.v.y * sy }),
+            v: dude.v,
+        }).collect();
+        Room { grid: self.grid.clone(), dudes, round: self.round + steps }
+    }
+    /* every robot is back at its start after this many steps */
+    pub fn period(&self) -> usize {
+        let (w, h) = (self.grid.width as usize, self.grid.height as usize);
+        w / gcd(w, h) * h
+    }
+    /*
+     * All distinct configurations, in order of their first appearance
+     * starting with the current one. Robots may repeat their positions well
+     * within one period, e.g. when none of them moves.
+     */
+    pub fn configurations(&self) -> impl Iterator<Item = Room> + '_ {
+        let mut seen = HashSet::new();
+        (0..self.period()).map(|s| self.at(s)).filter(move |r| {
+            let mut ps: Vec<Pos> = r.dudes.iter().map(|d| d.pos).collect();
+            ps.sort();
+            seen.insert(ps)
+        })
+    }
+    /*
+     * The picture is one big blob of touching robots, random frames are
+     * scattered. An empty room has no picture.
+     */
+    fn is_tree(&self) -> bool {
+        let mut g: Grid = Grid::new(self.grid.width, self.grid.height);
+        for dude in &self.dudes {
+            g.set(dude.pos, '#');
+        }
+        let robots = g.cells().iter().filter(|c| **c == '#').count();
+        let cluster = g.regions(Connectivity::Eight).into_iter()
+            .filter(|r| r.cell == '#')
+            .map(|r| r.area())
+            .max()
+            .unwrap_or(0);
+        robots > 0 && 5 * cluster >= 2 * robots
+    }
+    /*
+     * x positions repeat every width steps and y positions every height
+     * steps. The picture is the step where both axes are least spread out,
+     * combined from the two periods with the chinese remainder theorem.
+     * That needs coprime sizes, otherwise or when it finds no picture the
+     * whole period is searched.
+     */
+    pub fn tree(&self) -> Option<Room> {
+        let (w, h) = (self.grid.width, self.grid.height);
+        let tx = self.least_variance(w, |p| p.x)?;
+        let ty = self.least_variance(h, |p| p.y)?;
+        (0..h).map(|k| tx + k * w).find(|t| t % h == ty)
+            .map(|step| self.at(step as usize))
+            .filter(|r| r.is_tree())
+            .or_else(|| (0..self.period()).map(|s| self.at(s)).find(|r| r.is_tree()))
+    }
+    fn least_variance(&self, period: isize, axis: impl Fn(Pos) -> isize) -> Option<isize> {
+        let n = self.dudes.len() as isize;
+        (0..period).min_by_key(|&t| {
+            let (sum, sq) = self.dudes.iter()
+                .map(|d| axis(self.grid.wrap(d.pos + d.v * t)))
+                .fold((0, 0), |(s, q), v| (s + v, q + v * v));
+            n * sq - sum * sum
+        })
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+impl std::fmt::Display for Room {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut g: Grid<u8> = Grid::new(self.grid.width, self.grid.height);
+        for dude in &self.dudes {
+            let n = g.get(&dude.pos).unwrap();
+            g.set(dude.pos, (n + 1).min(9));
+        }
+        for y in 0..g.height {
+            let row: String = g.row(y).iter()
+                .map(|&n| if n == 0 { '.' } else { n.to_char() })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input = "
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+".to_string();
+        let grid = Grid::toroidal(11, 7);
+        let dudes = parse(&input).unwrap();
+        let mut r = Room {
+            grid, dudes, round: 0
+        };
+        r.run(100);
+
+        assert_eq!(r.clone().safety(), Some(12));
+        assert!(!r.is_tree());
+    }
+
+    #[test]
+    fn test_tree() {
+        let input = "
+p=5,0 v=0,0
+p=4,1 v=0,0
+p=6,1 v=0,0
+p=3,2 v=0,0
+p=7,2 v=0,0
+p=2,3 v=0,0
+p=8,3 v=0,0
+p=5,4 v=0,0
+p=5,5 v=0,0
+p=5,6 v=0,0
+".to_string();
+        let grid = Grid::toroidal(11, 7);
+        let dudes = parse(&input).unwrap();
+        let mut r = Room {
+            grid, dudes, round: 0
+        };
+        r.run(100);
+
+        assert!(r.is_tree());
+        assert_eq!(r.tree().map(|t| t.round), Some(100));
+        assert_eq!(r.to_string(), "
+.....#.....
+....#.#....
+...#...#...
+..#.....#..
+.....#.....
+.....#.....
+.....#.....
+".replace('#', "1")[1..]);
+    }
+
+    #[test]
+    fn test_tree_step() {
+        /* a packed 3x3 block, scattered by running it backwards */
+        let steps = 30;
+        let dudes = (0..9).map(|i| {
+            let v = Pos { x: i % 5 + 1, y: i % 3 - 1 };
+            let pos = Pos { x: 4 + i % 3, y: 2 + i / 3 } - v * steps;
+            Dude { pos: pos.wrap_in(11, 7), v }
+        }).collect();
+        let r = Room {
+            grid: Grid::toroidal(11, 7), dudes, round: 0
+        };
+        assert!(!r.is_tree());
+        let t = r.tree().unwrap();
+        assert_eq!(t.round, steps as usize);
+        assert!(t.is_tree());
+    }
+
+    #[test]
+    fn test_tree_not_coprime() {
+        /* the block of test_tree_step in a 12x8 room, sizes sharing a factor of 4 */
+        let steps = 29;
+        let dudes = (0..9).map(|i| {
+            let v = Pos { x: i % 5 + 1, y: i % 3 - 1 };
+            let pos = Pos { x: 4 + i % 3, y: 2 + i / 3 } - v * steps;
+            Dude { pos: pos.wrap_in(12, 8), v }
+        }).collect();
+        let r = Room {
+            grid: Grid::toroidal(12, 8), dudes, round: 0
+        };
+        assert_eq!(r.period(), 24);
+        let t = r.tree().unwrap();
+        assert_eq!(t.round, steps as usize % 24);
+        assert!(t.is_tree());
+        /* nobody there, no picture */
+        let r = Setup::parse("room=11x7\n").unwrap().room();
+        assert!(!r.is_tree());
+        assert!(r.tree().is_none());
+    }
+
+    #[test]
+    fn test_at() {
+        let input = "
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+".to_string();
+        let mut r = Setup::parse(&input).unwrap().room();
+        assert_eq!(r.period(), 77);
+        let at = r.at(5);
+        assert_eq!(at.dudes[0].pos, Pos { x: 1, y: 3 });
+        r.run(5);
+        assert_eq!(r.round, 5);
+        assert_eq!(r.dudes.iter().map(|d| d.pos).collect::<Vec<_>>(), at.dudes.iter().map(|d| d.pos).collect::<Vec<_>>());
+        let start = r.at(0).dudes.into_iter().map(|d| d.pos).collect::<Vec<_>>();
+        let far = r.at(1_000_000_000_000 * 77);
+        assert_eq!(far.dudes.into_iter().map(|d| d.pos).collect::<Vec<_>>(), start);
+        assert_eq!(far.round, 5 + 77_000_000_000_000);
+        assert_eq!(r.configurations().count(), 77);
+        assert_eq!(r.configurations().map(|c| c.to_string()).collect::<HashSet<_>>().len(), 77);
+        /* standing still, or coming back after half the period */
+        let s = Setup::parse("room=11x7\np=1,1 v=0,0\np=5,5 v=0,0").unwrap();
+        assert_eq!(s.room().configurations().count(), 1);
+        let s = Setup::parse("room=4x1\np=0,0 v=1,0\np=2,0 v=1,0").unwrap();
+        assert_eq!(s.room().period(), 4);
+        assert_eq!(s.room().configurations().map(|c| c.round).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_setup() {
+        let input = "
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+        let s = Setup::parse(input).unwrap();
+        assert_eq!((s.width, s.height, s.steps), (11, 7, 100));
+        assert_eq!(Day14::part1(&s).to_string(), "12");
+        let s = Setup::parse(&format!("room=12x7 steps=5{input}")).unwrap();
+        assert_eq!((s.width, s.height, s.steps), (12, 7, 5));
+        assert_eq!(s.room().safety(), None);
+        assert_eq!(Day14::part1(&s).to_string(), "no quadrants");
+        let s = Setup::parse("steps=7\np=50,50 v=1,1").unwrap();
+        assert_eq!((s.width, s.height, s.steps), (101, 103, 7));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+p=0,4 v=3,-3
+p=6,3 v=-1-3
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(3, 1, "p=6,3 v=-1-3", "'p=<x>,<y> v=<dx>,<dy>'")));
+        let input = "
+p=0,4 v=3,-3
+p=6,3 v=-1,3-
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(3, 12, "3-", "number")));
+        let input = "
+room=11,7
+p=0,4 v=3,-3
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(2, 6, "11,7", "'<width>x<height>'")));
+        let input = "
+size=11x7
+p=0,4 v=3,-3
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(2, 1, "size=11x7", "'room=<width>x<height>' or 'steps=<n>'")));
+        let input = "
+room=0x7
+p=0,4 v=3,-3
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(2, 6, "0x7", "positive '<width>x<height>'")));
+        let input = "
+room=11x-7
+p=0,4 v=3,-3
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(2, 6, "11x-7", "positive '<width>x<height>'")));
+    }
+
+}
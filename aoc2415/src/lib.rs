@@ -0,0 +1,434 @@
+use aoc_common::day::Solution;
+use aoc_common::direction::Direction::{self, *};
+use aoc_common::grid::{self, Grid, Pos};
+use aoc_common::parse::ParseError;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Warehouse;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut w = input.clone();
+        w.run();
+        w.gps()
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        let mut w = input.widen();
+        w.run();
+        w.gps()
+    }
+}
+
+#[derive(Clone,Copy,Hash,Debug,PartialEq,Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    BoxLeft,
+    BoxRight,
+    Robot,
+}
+
+impl grid::Block for Tile {
+    const EXPECTED: &'static str = "warehouse tile";
+    fn empty() -> Tile {
+        Tile::Empty
+    }
+    fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Empty),
+            '#' => Some(Tile::Wall),
+            'O' => Some(Tile::Box),
+            '[' => Some(Tile::BoxLeft),
+            ']' => Some(Tile::BoxRight),
+            '@' => Some(Tile::Robot),
+            _ => None,
+        }
+    }
+    fn to_char(&self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+            Tile::Box => 'O',
+            Tile::BoxLeft => '[',
+            Tile::BoxRight => ']',
+            Tile::Robot => '@',
+        }
+    }
+}
+
+/* the map with the robot on it, and the moves it will attempt */
+#[derive(Clone,Debug)]
+pub struct Warehouse {
+    map: Grid<Tile>,
+    robot: Pos,
+    moves: Vec<Direction>,
+}
+
+fn parse(input: &str) -> Result<Warehouse, ParseError> {
+    Warehouse::load(input, false)
+}
+
+impl Warehouse {
+    /*
+     * The map comes first, then after a blank line the moves, which may be
+     * spread over several lines. Boxes are 'O', or '[' and ']' side by side
+     * in a `wide` map.
+     */
+    fn load(input: &str, wide: bool) -> Result<Self, ParseError> {
+        let mut started = false;
+        let split = input.lines()
+            .find(|l| {
+                started |= !l.is_empty();
+                started && l.is_empty()
+            })
+            .map_or(input.len(), |l| l.as_ptr() as usize - input.as_ptr() as usize);
+        let expected = match wide {
+            true => "'.', '#', '[]' or robot '@'",
+            false => "'.', '#', 'O' or robot '@'",
+        };
+        let map: Grid<Tile> = Grid::parse(&input[..split]).map_err(|e| match e.expected == <Tile as grid::Block>::EXPECTED {
+            true => ParseError { expected: expected.to_string(), ..e },
+            false => e,
+        })?;
+        let token = |p: Pos| {
+            let ln = input[..split].lines().filter(|l| !l.is_empty()).nth(p.y as usize).unwrap();
+            let i = ln.char_indices().nth(p.x as usize).unwrap().0;
+            &ln[i..i + 1]
+        };
+        let mut robot = None;
+        for (p, t) in map.iter() {
+            let ok = match t {
+                Tile::Robot if robot.is_some() => return Err(ParseError::at(input, token(p), "a single robot '@'")),
+                Tile::Robot => {
+                    robot = Some(p);
+                    true
+                },
+                Tile::Box => !wide,
+                Tile::BoxLeft => wide && map.get(&(p + East)) == Some(Tile::BoxRight),
+                Tile::BoxRight => wide && map.get(&(p + West)) == Some(Tile::BoxLeft),
+                Tile::Empty | Tile::Wall => true,
+            };
+            if !ok {
+                return Err(ParseError::at(input, token(p), expected));
+            }
+        }
+        let robot = robot.ok_or(ParseError::at(input, &input[split..split], "robot '@'"))?;
+        let mut moves = vec![];
+        let rest = &input[split..];
+        for (i, c) in rest.char_indices() {
+            if c.is_whitespace() { continue; }
+            let d = Direction::from_char(c)
+                .ok_or(ParseError::at(input, &rest[i..i + c.len_utf8()], "move '^', '>', 'v' or '<'"))?;
+            moves.push(d);
+        }
+        Ok(Warehouse { map, robot, moves })
+    }
+    /* the second warehouse, everything but the robot twice as wide */
+    pub fn widen(&self) -> Warehouse {
+        let mut map = Grid::new(self.map.width * 2, self.map.height);
+        for (p, t) in self.map.iter() {
+            let (l, r) = match t {
+                Tile::Box => (Tile::BoxLeft, Tile::BoxRight),
+                Tile::Robot => (Tile::Robot, Tile::Empty),
+                t => (t, t),
+            };
+            map.set(Pos { x: p.x * 2, y: p.y }, l);
+            map.set(Pos { x: p.x * 2 + 1, y: p.y }, r);
+        }
+        let robot = Pos { x: self.robot.x * 2, y: self.robot.y };
+        Warehouse { map, robot, moves: self.moves.clone() }
+    }
+    /*
+     * Everything that moves along when the robot steps towards `d`, the
+     * robot first, or `None` if a wall is in the way. Pushing a wide box up
+     * or down pushes both of its halves, which may each push further boxes.
+     */
+    fn pushed(&self, d: Direction) -> Option<Vec<Pos>> {
+        let mut moved = vec![self.robot];
+        let mut seen = HashSet::from([self.robot]);
+        let mut i = 0;
+        while i < moved.len() {
+            let next = moved[i] + d;
+            i += 1;
+            let also = match self.map.get(&next)? {
+                Tile::Wall => return None,
+                Tile::Empty => continue,
+                Tile::BoxLeft if d == North || d == South => Some(next + East),
+                Tile::BoxRight if d == North || d == South => Some(next + West),
+                _ => None,
+            };
+            for p in [Some(next), also].into_iter().flatten() {
+                if seen.insert(p) {
+                    moved.push(p);
+                }
+            }
+        }
+        Some(moved)
+    }
+    /* attempts one move, tells whether the robot got anywhere */
+    fn step(&mut self, d: Direction) -> bool {
+        let Some(moved) = self.pushed(d) else {
+            return false;
+        };
+        let tiles: Vec<Tile> = moved.iter().map(|p| self.map.get(p).unwrap()).collect();
+        for p in &moved {
+            self.map.set(*p, Tile::Empty);
+        }
+        for (p, t) in moved.iter().zip(tiles) {
+            self.map.set(*p + d, t);
+        }
+        self.robot = self.robot + d;
+        true
+    }
+    fn run(&mut self) {
+        for d in self.moves.clone() {
+            self.step(d);
+        }
+    }
+    /* sum of 100 times the row plus the column of every box */
+    fn gps(&self) -> isize {
+        self.map.iter()
+            .filter(|(_, t)| matches!(t, Tile::Box | Tile::BoxLeft))
+            .map(|(p, _)| 100 * p.y + p.x)
+            .sum()
+    }
+    /* the map before and after every move, to follow the pushes */
+    pub fn render_steps(&self) -> String {
+        let mut w = self.clone();
+        let mut s = format!("Initial state:\n{}", w.map);
+        for d in &self.moves {
+            w.step(*d);
+            s.push_str(&format!("\nMove {}:\n{}", d, w.map));
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    const LARGE: &str = "
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+    #[test]
+    fn test_small() {
+        let mut w = parse(SMALL).unwrap();
+        assert_eq!(w.moves.len(), 15);
+        w.run();
+        assert_eq!(w.robot, Pos { x: 4, y: 4 });
+        assert_eq!(w.map.to_string(), "
+########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########
+"[1..]);
+        assert_eq!(w.gps(), 2028);
+    }
+
+    #[test]
+    fn test_large() {
+        let w = parse(LARGE).unwrap();
+        assert_eq!(Day15::part1(&w).to_string(), "10092");
+        assert_eq!(Day15::part2(&w).to_string(), "9021");
+    }
+
+    #[test]
+    fn test_wide() {
+        let input = "
+#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^
+";
+        let mut w = parse(input).unwrap().widen();
+        assert_eq!(w.map.to_string(), "
+##############
+##......##..##
+##..........##
+##....[][]@.##
+##....[]....##
+##..........##
+##############
+"[1..]);
+        w.run();
+        assert_eq!(w.map.to_string(), "
+##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############
+"[1..]);
+        assert_eq!(w.gps(), 105 + 207 + 306);
+    }
+
+    #[test]
+    fn test_cascade_blocked() {
+        /* the upper box is stuck under the wall, so the lower one cannot move */
+        let input = "
+##########
+##......##
+##..#...##
+##..[]..##
+##...[].##
+##...@..##
+##########
+
+^
+";
+        let mut w = Warehouse::load(input, true).unwrap();
+        let before = w.map.clone();
+        assert_eq!(w.pushed(North), None);
+        assert!(!w.step(North));
+        assert_eq!(w.map, before);
+        /* without the wall both boxes go up */
+        w.map.set(Pos { x: 4, y: 2 }, Tile::Empty);
+        assert_eq!(w.pushed(North).map(|m| m.len()), Some(5));
+        assert!(w.step(North));
+        assert_eq!(w.robot, Pos { x: 5, y: 4 });
+        assert_eq!(w.gps(), 204 + 305);
+    }
+
+    #[test]
+    fn test_render_steps() {
+        let w = parse("
+#####
+#@O.#
+#####
+
+>>
+").unwrap();
+        assert_eq!(w.render_steps(), "Initial state:
+#####
+#@O.#
+#####
+
+Move >:
+#####
+#.@O#
+#####
+
+Move >:
+#####
+#.@O#
+#####
+");
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = SMALL.replace('\n', "\r\n");
+        let w = parse(&input).unwrap();
+        assert_eq!(w.moves.len(), 15);
+        assert_eq!(Day15::part1(&w).to_string(), "2028");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+####
+#@x#
+####
+
+<>
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 3, "x", "'.', '#', 'O' or robot '@'")));
+        let input = "
+####
+#@@#
+####
+
+<>
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 3, "@", "a single robot '@'")));
+        let input = "
+####
+#..#
+####
+
+<>
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(5, 1, "", "robot '@'")));
+        let input = "
+####
+#@.#
+####
+
+<>
+^x
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(7, 2, "x", "move '^', '>', 'v' or '<'")));
+        /* wide boxes only in a wide map, and only as pairs */
+        let input = "
+######
+#@[].#
+######
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 3, "[", "'.', '#', 'O' or robot '@'")));
+        assert!(Warehouse::load(input, true).is_ok());
+        let input = "
+######
+#@[..#
+######
+";
+        assert_eq!(Warehouse::load(input, true).err(), Some(ParseError::new(3, 3, "[", "'.', '#', '[]' or robot '@'")));
+        let input = "
+######
+#@O.]#
+######
+";
+        assert_eq!(Warehouse::load(input, true).err(), Some(ParseError::new(3, 3, "O", "'.', '#', '[]' or robot '@'")));
+    }
+}
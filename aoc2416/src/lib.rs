@@ -0,0 +1,618 @@
+use aoc_common::day::Solution;
+use aoc_common::direction::Direction::{self, *};
+use aoc_common::parse::ParseError;
+use aoc_common::grid::{Pos, Grid};
+use aoc_common::search::{dijkstra, Paths};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Maze;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut grid = input.clone();
+        grid.solve();
+        grid.cost_to_end().map_or("unsolved".to_string(), |c| c.to_string())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        let mut grid = input.clone();
+        grid.solve();
+        grid.tiles_to_end().map_or("unsolved".to_string(), |t| t.to_string())
+    }
+}
+
+fn parse(input: &str) -> Result<Maze, ParseError> {
+    for line in input.lines() {
+        if line.is_empty() { continue; }
+        if !line.starts_with('#') {
+            return Err(ParseError::at(input, line, "maze row starting with '#'"));
+        }
+    }
+    Maze::parse(input)
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone,Copy,Hash,Debug,PartialEq,Eq)]
+enum Cost {
+    STEP = 1,
+    TURN = 1000,
+}
+
+/*
+ * Prices moves through a maze. `None` forbids a move, like entering a wall
+ * or turning around on the spot.
+ */
+pub trait CostModel {
+    /* entering a tile */
+    fn step(&self, tile: char) -> Option<usize>;
+    /* turning on the spot by `angle` times 45 degrees */
+    fn turn(&self, angle: usize) -> Option<usize>;
+}
+
+/* walls block, every step costs one, every 90 degrees turned a thousand */
+#[derive(Clone,Copy,Debug,Default)]
+pub struct Reindeer;
+
+impl CostModel for Reindeer {
+    fn step(&self, tile: char) -> Option<usize> {
+        match tile {
+            '#' => None,
+            _ => Some(Cost::STEP as usize),
+        }
+    }
+    fn turn(&self, angle: usize) -> Option<usize> {
+        Some(angle / 2 * (Cost::TURN as usize))
+    }
+}
+
+/* turns to face the next cell and steps onto it, `None` if the model forbids either */
+fn cost(model: &impl CostModel, grid: &Grid, d: &Direction, p: &Pos, c: &Pos, newd: Option<&mut Direction>) -> Option<usize> {
+    let d2 = Direction::try_from(*c - *p).expect("neighbouring cells");
+    if let Some(newd) = newd {
+        *newd = d2;
+    }
+    Some(model.turn(d.angle(&d2))? + model.step(grid.get(c)?)?)
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Path {
+    start_direction: Direction,
+    cells: Vec<Pos>,
+}
+
+impl std::hash::Hash for Path {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
+impl Path {
+    fn new(start: Pos, start_direction: Direction) -> Self {
+        let mut cells = Vec::<Pos>::with_capacity(20);
+        cells.push(start);
+        Self { cells, start_direction }
+    }
+    /* walking the path through `grid` as priced by `model` */
+    pub fn cost(&self, model: &impl CostModel, grid: &Grid) -> Option<usize> {
+        let mut cells = self.cells.iter();
+        let mut s = 0;
+        let mut d = self.start_direction;
+        let mut p = cells.next().unwrap();
+        for c in cells {
+            s += cost(model, grid, &d.clone(), p, c, Some(&mut d))?;
+            p = c;
+        }
+        Some(s)
+    }
+    pub fn print(&self, model: &impl CostModel, grid: &Grid) {
+        let mut cells = self.cells.iter();
+        let mut s = Some(0);
+        let mut d = self.start_direction;
+        let mut p = cells.next().unwrap();
+        for c in cells {
+            let step = cost(model, grid, &d.clone(), p, c, Some(&mut d));
+            s = s.zip(step).map(|(s, step)| s + step);
+            p = c;
+            print!("{}", d);
+        }
+        match s {
+            Some(s) => println!(" cost: {}", s),
+            None => println!(" forbidden"),
+        }
+    }
+}
+
+type State = (Pos, Direction);
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Style {
+    Plain,
+    Ansi,
+}
+
+
+#[derive(Clone,Debug)]
+pub struct Maze {
+    start: Vec<State>,
+    end: Vec<Pos>,
+    grid: Grid,
+    paths: Option<Paths<State, usize>>,
+}
+
+impl Maze {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::load(input, false)
+    }
+    /*
+     * 'S' starts facing east, '^', '>', 'v' and '<' start facing that way,
+     * 'E' is the end. Only `multi` mazes may have several of either.
+     */
+    pub fn load(input: &str, multi: bool) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input)?;
+        let (mut start, mut end) = (vec![], vec![]);
+        let mut y = 0;
+        for ln in input.lines() {
+            if ln.is_empty() { continue; }
+            for (x, (i, c)) in ln.char_indices().enumerate() {
+                let pos = Pos { x: x as isize, y };
+                let token = &ln[i..i + c.len_utf8()];
+                let heading = match c {
+                    'S' => Some(East),
+                    c => Direction::from_char(c),
+                };
+                if let Some(d) = heading {
+                    if !multi && !start.is_empty() {
+                        return Err(ParseError::at(input, token, "a single start"));
+                    }
+                    start.push((pos, d));
+                } else if c == 'E' {
+                    if !multi && !end.is_empty() {
+                        return Err(ParseError::at(input, token, "a single end 'E'"));
+                    }
+                    end.push(pos);
+                }
+            }
+            y += 1;
+        }
+        if start.is_empty() {
+            return Err(ParseError::end(input, "start 'S'"));
+        }
+        if end.is_empty() {
+            return Err(ParseError::end(input, "end 'E'"));
+        }
+        Ok(Maze { start, end, paths: None, grid })
+    }
+    /* step ahead or turn on the spot to any other heading */
+    fn moves(&self, model: &impl CostModel, (pos, d): &State) -> Vec<(State, usize)> {
+        let mut moves: Vec<(State, usize)> = Direction::CARDINAL.into_iter()
+            .filter(|d2| d2 != d)
+            .filter_map(|d2| Some(((*pos, d2), model.turn(d.angle(&d2))?)))
+            .collect();
+        let ahead = *pos + *d;
+        if let Some(c) = self.grid.get(&ahead).and_then(|t| model.step(t)) {
+            moves.push(((ahead, *d), c));
+        }
+        moves
+    }
+    pub fn solve(&mut self) {
+        self.solve_with(&Reindeer);
+    }
+    pub fn solve_with(&mut self, model: &impl CostModel) {
+        self.paths = Some(dijkstra(
+            self.start.iter().copied(),
+            |s| self.moves(model, s),
+            |(pos, _)| self.end.contains(pos),
+        ));
+    }
+    pub fn cost_to_end(&self) -> Option<usize> {
+        self.paths.as_ref()?.best()
+    }
+    pub fn tiles_to_end(&self) -> Option<usize> {
+        let paths = self.paths.as_ref()?;
+        paths.best()?;
+        let tiles: HashSet<Pos> = paths.nodes().into_iter().map(|(pos, _)| pos).collect();
+        Some(tiles.len())
+    }
+    /*
+     * The maze with all tiles on some best path as 'O', one best path drawn
+     * with the heading it leaves each tile in and '+' where it turns.
+     */
+    pub fn render(&self, style: Style) -> Option<String> {
+        let paths = self.paths.as_ref()?;
+        let best: HashSet<Pos> = paths.nodes().into_iter().map(|(pos, _)| pos).collect();
+        let mut path: HashMap<Pos, Vec<Direction>> = HashMap::new();
+        for (pos, d) in paths.path()? {
+            path.entry(pos).or_default().push(d);
+        }
+        let mut s = String::new();
+        for y in 0..self.grid.height {
+            for x in 0..self.grid.width {
+                let pos = Pos { x, y };
+                let c = self.grid.get(&pos).unwrap();
+                let (c, color) = match path.get(&pos) {
+                    _ if c == 'S' || c == 'E' => (c, "1;36"),
+                    Some(d) if d.len() > 1 => ('+', "1;31"),
+                    Some(d) => (d[0].to_char(), "1;33"),
+                    None if best.contains(&pos) => ('O', "32"),
+                    None if c == '#' => (c, "2"),
+                    None => (c, ""),
+                };
+                match style {
+                    Style::Ansi if !color.is_empty() => s.push_str(&format!("\x1b[{color}m{c}\x1b[0m")),
+                    _ => s.push(c),
+                }
+            }
+            s.push('\n');
+        }
+        Some(s)
+    }
+    /* one of the best paths, as the tiles walked */
+    pub fn best_path(&self) -> Option<Path> {
+        let states = self.paths.as_ref()?.path()?;
+        let (start, start_direction) = states[0];
+        let mut path = Path::new(start, start_direction);
+        for (pos, _) in states {
+            if path.cells.last() != Some(&pos) {
+                path.cells.push(pos);
+            }
+        }
+        Some(path)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path0() {
+        let p0 = Path::new(Pos { x: 1, y: 1 }, East);
+        assert_eq!(p0.cost(&Reindeer, &Grid::new(3, 3)), Some(0));
+    }
+
+    #[test]
+    fn path1() {
+        let p = Path{ cells: [Pos { x: 1, y: 1 }, Pos { x: 2, y: 1 }].to_vec(), start_direction: East, };
+        assert_eq!(p.cost(&Reindeer, &Grid::new(3, 3)), Some(1));
+    }
+
+    #[test]
+    fn path2() {
+        let p = Path{ cells: [Pos { x: 1, y: 1 }, Pos { x: 1, y: 2 }].to_vec(), start_direction: East, };
+        assert_eq!(p.cost(&Reindeer, &Grid::new(3, 3)), Some(1001));
+    }
+
+    #[test]
+    fn path3() {
+        let p = Path{ cells: [Pos { x: 1, y: 1 }, Pos { x: 0, y: 1 }].to_vec(), start_direction: East, };
+        assert_eq!(p.cost(&Reindeer, &Grid::new(3, 3)), Some(2001));
+    }
+
+    #[test]
+    fn path4() {
+        let p = Path{ cells: [Pos { x: 1, y: 1 }, Pos { x: 0, y: 1 }, Pos { x: 0, y: 2 }].to_vec(), start_direction: East, };
+        assert_eq!(p.cost(&Reindeer, &Grid::new(3, 3)), Some(3002));
+    }
+
+    #[test]
+    fn test_smallest() {
+        let input = "
+###
+#E#
+#S#
+###
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 1001);
+    }
+
+    #[test]
+    fn test_smallest_plus() {
+        let input = "
+###
+#E#
+#.#
+#S#
+###
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 1002);
+    }
+
+    #[test]
+    fn test_options() {
+        let input = "
+#####
+#..E#
+#S..#
+#####
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 1003);
+    }
+
+    #[test]
+    fn test_small_alt() {
+        let input = "
+########
+#....#E#
+#.#..#.#
+#.#..#.#
+#S#....#
+########
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 5014);
+    }
+
+    #[test]
+    fn test_loop() {
+        let input = "
+#############
+#..........E#
+###.#.#####.#
+#...#.....#.#
+#.#.#.###.#.#
+#.....#...#.#
+#.###.#.#.#.#
+#S..#.....#.#
+#############
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 4016);
+    }
+
+    #[test]
+    fn test() {
+        let input = "
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 7036);
+        assert_eq!(grid.tiles_to_end().expect("not solved"), 45);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        assert_eq!(grid.render(Style::Plain), None);
+        grid.solve();
+        assert_eq!(grid.render(Style::Plain).unwrap(), "
+###############
+#.......#....E#
+#.#.###.#.###^#
+#.....#.#...#^#
+#.###.#####.#^#
+#.#.#.......#^#
+#.#.#####.###^#
+#..OO+>>>>>+#^#
+###O#^#####v#^#
+#OOO#^....#v#^#
+#O#O#^###.#v#^#
+#+>>>+#...#v#^#
+#^###.#.#.#v#^#
+#S..#.....#+>+#
+###############
+"[1..]);
+        let ansi = grid.render(Style::Ansi).unwrap();
+        assert!(ansi.contains("\x1b[1;36mE\x1b[0m"));
+        assert_eq!(ansi.matches("\x1b[1;31m").count(), 6);
+        assert_eq!(ansi.matches("\x1b[32mO").count(), 8);
+        assert_eq!(ansi.replace("\x1b[0m", "").split("\x1b[").map(|s| s.split_once('m').map_or(s, |(_, s)| s)).collect::<String>(),
+            grid.render(Style::Plain).unwrap());
+    }
+
+    #[test]
+    fn test_bigger() {
+        let input = "
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+".to_string();
+        let mut grid = parse(&input)
+            .expect("Parse failed");
+        grid.solve();
+        assert_eq!(grid.cost_to_end().expect("not solved"), 11048);
+        assert_eq!(grid.tiles_to_end().expect("not solved"), 64);
+        assert_eq!(grid.best_path().expect("not solved").cost(&Reindeer, &grid.grid), Some(11048));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+#####
+#..E#
+S...#
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 1, "S...#", "maze row starting with '#'")));
+        let input = "
+#####
+#..E#
+#S..##
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 1, "#S..##", "row of width 5")));
+        let input = "
+#####
+#...#
+#S..#
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(6, 1, "", "end 'E'")));
+        let input = "
+#####
+#..E#
+#...#
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(6, 1, "", "start 'S'")));
+        let input = "
+#####
+#E.E#
+#S..#
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(3, 4, "E", "a single end 'E'")));
+        let input = "
+#####
+#..E#
+#S.^#
+#####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 4, "^", "a single start")));
+    }
+
+    #[test]
+    fn test_markers() {
+        let input = "
+#######
+#.....#
+#.#E#.#
+#.....#
+#######
+".to_string();
+        /* the end is right above the start */
+        for (start, cost) in [('^', 1), ('<', 1001), ('>', 1001), ('v', 2001), ('S', 1001)] {
+            let input = input.replace("#.....#\n#######", &format!("#..{start}..#\n#######"));
+            let mut grid = parse(&input).unwrap();
+            grid.solve();
+            assert_eq!(grid.cost_to_end(), Some(cost));
+        }
+    }
+
+    struct Boat;
+
+    /* water is slow, turning is cheap but there is no turning back */
+    impl CostModel for Boat {
+        fn step(&self, tile: char) -> Option<usize> {
+            match tile {
+                '#' => None,
+                '~' => Some(5),
+                _ => Some(1),
+            }
+        }
+        fn turn(&self, angle: usize) -> Option<usize> {
+            match angle {
+                4 => None,
+                a => Some(a / 2),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cost_model() {
+        let input = "
+#########
+#.......#
+#.#####.#
+#S~~~~~E#
+#########
+".to_string();
+        let mut grid = parse(&input).unwrap();
+        grid.solve();
+        assert_eq!(grid.cost_to_end(), Some(6));
+        assert_eq!(grid.best_path().unwrap().cost(&Boat, &grid.grid), Some(5 * 5 + 1));
+        grid.solve_with(&Boat);
+        assert_eq!(grid.cost_to_end(), Some(3 + 2 + 6 + 2));
+        assert_eq!(grid.tiles_to_end(), Some(11));
+        let path = grid.best_path().unwrap();
+        assert_eq!(path.cost(&Boat, &grid.grid), grid.cost_to_end());
+        assert_eq!(path.cost(&Reindeer, &grid.grid), Some(3010));
+        let mut grid = parse(&input.replace("#S~", "#<~")).unwrap();
+        grid.solve();
+        assert_eq!(grid.cost_to_end(), Some(2006));
+        grid.solve_with(&Boat);
+        assert_eq!(grid.cost_to_end(), Some(13));
+    }
+
+    #[test]
+    fn test_several() {
+        let input = "
+#########
+#S.....E#
+#.#####.#
+#E......#
+#########
+".to_string();
+        let mut grid = Maze::load(&input, true).unwrap();
+        assert_eq!(grid.start.len(), 1);
+        assert_eq!(grid.end.len(), 2);
+        grid.solve();
+        assert_eq!(grid.cost_to_end(), Some(6));
+        assert_eq!(grid.tiles_to_end(), Some(7));
+        let mut grid = Maze::load(&input.replace("#S", "#.").replace("#.#####.#", "#.#####v#"), true).unwrap();
+        grid.solve();
+        assert_eq!(grid.cost_to_end(), Some(1007));
+        let mut grid = Maze::load(&input.replace("#.#####.#", "#.#####v#"), true).unwrap();
+        assert_eq!(grid.start.len(), 2);
+        grid.solve();
+        assert_eq!(grid.cost_to_end(), Some(6));
+    }
+}
@@ -0,0 +1,392 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::{number, ParseError};
+use aoc_common::progress;
+use std::fmt::Display;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Computer;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut c = input.clone();
+        c.run().map_or("no halt".to_string(), join)
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        quine(input).map_or("none".to_string(), |a| a.to_string())
+    }
+}
+
+type Word = u64;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone,Copy,Hash,Debug,PartialEq,Eq)]
+enum Op {
+    ADV,
+    BXL,
+    BST,
+    JNZ,
+    BXC,
+    OUT,
+    BDV,
+    CDV,
+}
+
+impl Op {
+    const ALL: [Op; 8] = [Op::ADV, Op::BXL, Op::BST, Op::JNZ, Op::BXC, Op::OUT, Op::BDV, Op::CDV];
+
+    fn from(opcode: u8) -> Op {
+        Self::ALL[opcode as usize & 7]
+    }
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            Op::ADV => "adv",
+            Op::BXL => "bxl",
+            Op::BST => "bst",
+            Op::JNZ => "jnz",
+            Op::BXC => "bxc",
+            Op::OUT => "out",
+            Op::BDV => "bdv",
+            Op::CDV => "cdv",
+        }
+    }
+    /* whether the operand names a register rather than being a literal */
+    fn combo(&self) -> bool {
+        matches!(self, Op::ADV | Op::BST | Op::OUT | Op::BDV | Op::CDV)
+    }
+}
+
+/* the operand as written in the disassembly: 0-3, a, b, c or ? for 7 */
+fn operand(op: Op, v: u8) -> String {
+    match v {
+        4..=6 if op.combo() => ["a", "b", "c"][v as usize - 4].to_string(),
+        7 if op.combo() => "?".to_string(),
+        _ if op == Op::BXC => "-".to_string(),
+        v => v.to_string(),
+    }
+}
+
+/* the programs in these puzzles halt after a few hundred instructions */
+const MAX_STEPS: usize = 1 << 20;
+/* instructions run between checks for cancellation */
+const REPORT_EVERY: usize = 1 << 12;
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Computer {
+    reg: [Word; 3],
+    ip: usize,
+    program: Vec<u8>,
+    out: Vec<u8>,
+}
+
+/*
+ * Register A: 729
+ * Register B: 0
+ * Register C: 0
+ *
+ * Program: 0,1,5,4,3,0
+ */
+fn parse(input: &str) -> Result<Computer, ParseError> {
+    let mut lines = input.lines().filter(|l| !l.is_empty());
+    let mut reg = [0; 3];
+    for (r, name) in reg.iter_mut().zip(["A", "B", "C"]) {
+        let expected = format!("'Register {name}: <value>'");
+        let line = lines.next().ok_or(ParseError::end(input, expected.as_str()))?;
+        let value = line.strip_prefix(&format!("Register {name}: "))
+            .ok_or(ParseError::at(input, line, expected))?;
+        *r = number(input, value)?;
+    }
+    let expected = "'Program: <opcode>,<operand>,...'";
+    let line = lines.next().ok_or(ParseError::end(input, expected))?;
+    let program = line.strip_prefix("Program: ")
+        .ok_or(ParseError::at(input, line, expected))?
+        .split(',')
+        .map(|v| match number::<u8>(input, v) {
+            Ok(n) if n < 8 => Ok(n),
+            _ => Err(ParseError::at(input, v, "3-bit number")),
+        })
+        .collect::<Result<Vec<u8>, ParseError>>()?;
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at(input, line, "end of input"));
+    }
+    Ok(Computer { reg, ip: 0, program, out: vec![] })
+}
+
+fn join(out: &[u8]) -> String {
+    out.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/* one instruction per line, prefixed by its address */
+pub fn disassemble(program: &[u8]) -> String {
+    program.chunks(2)
+        .enumerate()
+        .map(|(i, ins)| match ins {
+            [op, v] => {
+                let op = Op::from(*op);
+                format!("{:2}: {} {}\n", 2 * i, op.mnemonic(), operand(op, *v))
+            },
+            _ => format!("{:2}: ?\n", 2 * i),
+        })
+        .collect()
+}
+
+impl Computer {
+    pub fn with_a(&self, a: Word) -> Computer {
+        let mut c = self.clone();
+        c.reg = [a, self.reg[1], self.reg[2]];
+        c.ip = 0;
+        c.out.clear();
+        c
+    }
+    fn combo(&self, v: u8) -> Option<Word> {
+        match v {
+            0..=3 => Some(v as Word),
+            4..=6 => Some(self.reg[v as usize - 4]),
+            _ => None,
+        }
+    }
+    /* the instruction at the instruction pointer, if any */
+    fn current(&self) -> Option<(Op, u8)> {
+        match self.program.get(self.ip..self.ip + 2)? {
+            [op, v] => Some((Op::from(*op), *v)),
+            _ => None,
+        }
+    }
+    /*
+     * Executes one instruction. Returns false once halted, either by
+     * running off the program or on the reserved combo operand 7.
+     */
+    pub fn step(&mut self) -> bool {
+        let Some((op, v)) = self.current() else {
+            return false;
+        };
+        let value = if op.combo() {
+            match self.combo(v) {
+                Some(value) => value,
+                None => return false,
+            }
+        } else {
+            v as Word
+        };
+        let [a, b, c] = self.reg;
+        let div = |n: Word| a.checked_shr(n.try_into().unwrap_or(u32::MAX)).unwrap_or(0);
+        self.ip += 2;
+        match op {
+            Op::ADV => self.reg[0] = div(value),
+            Op::BXL => self.reg[1] = b ^ value,
+            Op::BST => self.reg[1] = value & 7,
+            Op::JNZ => if a != 0 { self.ip = value as usize },
+            Op::BXC => self.reg[1] = b ^ c,
+            Op::OUT => self.out.push((value & 7) as u8),
+            Op::BDV => self.reg[1] = div(value),
+            Op::CDV => self.reg[2] = div(value),
+        }
+        true
+    }
+    /*
+     * The output once halted, `None` if the program is still going after
+     * `MAX_STEPS` instructions or the progress reporter cancels it.
+     */
+    pub fn run(&mut self) -> Option<&[u8]> {
+        let progress = progress::current();
+        for n in 1..=MAX_STEPS {
+            if !self.step() {
+                return Some(&self.out);
+            }
+            if n.is_multiple_of(REPORT_EVERY) && progress.cancelled() { break; }
+        }
+        None
+    }
+    /* runs to the end, listing the registers before every instruction */
+    pub fn trace(&mut self) -> String {
+        let mut s = String::new();
+        let mut halted = false;
+        for _ in 0..MAX_STEPS {
+            let Some((op, v)) = self.current() else {
+                halted = true;
+                break;
+            };
+            let [a, b, c] = self.reg;
+            s.push_str(&format!("{:2}: {} {}  a={a} b={b} c={c}\n", self.ip, op.mnemonic(), operand(op, v)));
+            if !self.step() {
+                halted = true;
+                break;
+            }
+        }
+        match halted {
+            true => s.push_str(&format!("out {}\n", join(&self.out))),
+            false => s.push_str(&format!("no halt after {MAX_STEPS} steps\n")),
+        }
+        s
+    }
+}
+
+/*
+ * Smallest value of register A that makes the program print itself.
+ * Assumes the usual shape of these programs: a loop printing one value
+ * per round from the low bits of A, then dropping three bits of A until it
+ * is zero. The last value printed thus only depends on the top three bits,
+ * so A is built from the back, three bits per printed value, keeping every
+ * prefix that reproduces the tail of the program.
+ */
+fn quine(c: &Computer) -> Option<Word> {
+    let n = c.program.len();
+    let mut found: Option<Word> = None;
+    let mut todo = vec![(0 as Word, n)];
+    let progress = progress::current();
+    while let Some((a, left)) = todo.pop() {
+        if progress.cancelled() { break; }
+        if left == 0 {
+            found = Some(found.map_or(a, |f| f.min(a)));
+            continue;
+        }
+        for bits in (0..8).rev() {
+            let Some(a2) = a.checked_mul(8).map(|a| a | bits) else { continue };
+            if a2 == 0 { continue; }
+            if c.with_a(a2).run() == Some(&c.program[left - 1..]) {
+                todo.push((a2, left - 1));
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn computer(reg: [Word; 3], program: &[u8]) -> Computer {
+        Computer { reg, ip: 0, program: program.to_vec(), out: vec![] }
+    }
+
+    #[test]
+    fn test_instructions() {
+        let mut c = computer([0, 0, 9], &[2, 6]);
+        c.run();
+        assert_eq!(c.reg[1], 1);
+        let mut c = computer([10, 0, 0], &[5, 0, 5, 1, 5, 4]);
+        assert_eq!(c.run(), Some(&[0, 1, 2][..]));
+        let mut c = computer([2024, 0, 0], &[0, 1, 5, 4, 3, 0]);
+        assert_eq!(join(c.run().unwrap()), "4,2,5,6,7,7,7,7,3,1,0");
+        assert_eq!(c.reg[0], 0);
+        let mut c = computer([0, 29, 0], &[1, 7]);
+        c.run();
+        assert_eq!(c.reg[1], 26);
+        let mut c = computer([0, 2024, 43690], &[4, 0]);
+        c.run();
+        assert_eq!(c.reg[1], 44354);
+    }
+
+    #[test]
+    fn test_reserved_operand() {
+        let mut c = computer([8, 0, 0], &[0, 1, 5, 7, 5, 4]);
+        assert_eq!(c.run(), Some(&[][..]));
+        assert_eq!((c.reg[0], c.ip), (4, 2));
+    }
+
+    #[test]
+    fn test_no_halt() {
+        let c = computer([1, 0, 0], &[3, 0]);
+        assert_eq!(c.clone().run(), None);
+        assert_eq!(Day17::part1(&c).to_string(), "no halt");
+        assert_eq!(quine(&c), None);
+        /* printing forever, stopped at the first check */
+        let mut c = computer([1, 0, 0], &[5, 4, 3, 0]);
+        let cancel = progress::Cancel::new();
+        cancel.cancel();
+        assert!(progress::with(cancel, || c.run().is_none()));
+        assert_eq!(c.out.len(), REPORT_EVERY / 2);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+        let c = parse(input).unwrap();
+        assert_eq!(c, computer([729, 0, 0], &[0, 1, 5, 4, 3, 0]));
+        assert_eq!(Day17::part1(&c).to_string(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+        let c = parse(input).unwrap();
+        assert_eq!(quine(&c), Some(117440));
+        assert_eq!(c.with_a(117440).run(), Some(&c.program[..]));
+        /* the xor and shift heavy shape of the real inputs */
+        let c = computer([0, 0, 0], &[2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0]);
+        let a = quine(&c).unwrap();
+        assert_eq!(c.with_a(a).run(), Some(&c.program[..]));
+        /* nothing prints a program that does not print at all */
+        assert_eq!(quine(&computer([0, 0, 0], &[0, 3, 3, 0])), None);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(disassemble(&[0, 3, 5, 4, 3, 0, 4, 1, 7]), "
+ 0: adv 3
+ 2: out a
+ 4: jnz 0
+ 6: bxc -
+ 8: ?
+"[1..]);
+        assert_eq!(disassemble(&[2, 7, 1, 5, 6, 6]), " 0: bst ?\n 2: bxl 5\n 4: bdv c\n");
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut c = computer([10, 0, 0], &[0, 1, 5, 4, 3, 0]);
+        assert_eq!(c.trace(), "
+ 0: adv 1  a=10 b=0 c=0
+ 2: out a  a=5 b=0 c=0
+ 4: jnz 0  a=5 b=0 c=0
+ 0: adv 1  a=5 b=0 c=0
+ 2: out a  a=2 b=0 c=0
+ 4: jnz 0  a=2 b=0 c=0
+ 0: adv 1  a=2 b=0 c=0
+ 2: out a  a=1 b=0 c=0
+ 4: jnz 0  a=1 b=0 c=0
+ 0: adv 1  a=1 b=0 c=0
+ 2: out a  a=0 b=0 c=0
+ 4: jnz 0  a=0 b=0 c=0
+out 5,2,1,0
+"[1..]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+Register A: 729
+Register X: 0
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(3, 1, "Register X: 0", "'Register B: <value>'")));
+        let input = "
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,8,4
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(6, 14, "8", "3-bit number")));
+        let input = "
+Register A: 729
+Register B: 0
+Register C: 0
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(5, 1, "", "'Program: <opcode>,<operand>,...'")));
+    }
+}
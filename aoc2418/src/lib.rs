@@ -0,0 +1,202 @@
+use aoc_common::day::Solution;
+use aoc_common::grid::{Area, Grid, Pos};
+use aoc_common::parse::{number, ParseError};
+use aoc_common::search::bfs;
+use std::fmt::Display;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Memory;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Memory::parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        input.shortest(input.fallen).map_or("unreachable".to_string(), |s| s.to_string())
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        input.blocking().map_or("none".to_string(), |p| format!("{},{}", p.x, p.y))
+    }
+}
+
+/*
+ * size=7x7 bytes=12
+ * 5,4
+ *
+ * The header line is optional, so are both of its settings. Without a
+ * size, bytes inside 7x7 are the example with 12 bytes fallen, anything
+ * else is the 71x71 puzzle memory with 1024.
+ */
+#[derive(Clone,Debug)]
+pub struct Memory {
+    width: isize,
+    height: isize,
+    fallen: usize,
+    bytes: Vec<Pos>,
+}
+
+impl Memory {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines().filter(|l| !l.is_empty()).peekable();
+        let (mut size, mut fallen) = (None, None);
+        if let Some(header) = lines.next_if(|l| l.contains('=')) {
+            for token in header.split_whitespace() {
+                match token.split_once('=') {
+                    Some(("size", v)) => {
+                        let (w, h) = v.split_once('x')
+                            .ok_or(ParseError::at(input, v, "'<width>x<height>'"))?;
+                        let (w, h): (isize, isize) = (number(input, w)?, number(input, h)?);
+                        if w <= 0 || h <= 0 {
+                            return Err(ParseError::at(input, v, "positive '<width>x<height>'"));
+                        }
+                        size = Some((w, h));
+                    },
+                    Some(("bytes", v)) => fallen = Some(number(input, v)?),
+                    _ => return Err(ParseError::at(input, token, "'size=<width>x<height>' or 'bytes=<n>'")),
+                }
+            }
+        }
+        let bytes = lines
+            .map(|l| {
+                let (x, y) = l.split_once(',').ok_or(ParseError::at(input, l, "'<x>,<y>'"))?;
+                Ok((l, Pos { x: number(input, x)?, y: number(input, y)? }))
+            })
+            .collect::<Result<Vec<(&str, Pos)>, ParseError>>()?;
+        let (width, height, default) = match size {
+            Some((w, h)) => (w, h, 1024),
+            None => Self::detect(bytes.iter().map(|(_, p)| *p)),
+        };
+        let area = Area::from(Pos { x: 0, y: 0 }, Pos { x: width - 1, y: height - 1 });
+        if let Some((l, _)) = bytes.iter().find(|(_, p)| !area.contains(*p)) {
+            return Err(ParseError::at(input, l, format!("byte within {width}x{height}")));
+        }
+        let bytes: Vec<Pos> = bytes.into_iter().map(|(_, p)| p).collect();
+        let fallen = fallen.unwrap_or(default).min(bytes.len());
+        Ok(Memory { width, height, fallen, bytes })
+    }
+    fn detect(mut bytes: impl Iterator<Item = Pos>) -> (isize, isize, usize) {
+        let example = Area::from(Pos { x: 0, y: 0 }, Pos { x: 6, y: 6 });
+        match bytes.all(|p| example.contains(p)) {
+            true => (7, 7, 12),
+            false => (71, 71, 1024),
+        }
+    }
+    /* the memory after the first `n` bytes fell */
+    fn grid(&self, n: usize) -> Grid {
+        let mut g = Grid::new(self.width, self.height);
+        for p in &self.bytes[..n] {
+            g.set(*p, '#');
+        }
+        g
+    }
+    /* steps from the top left to the bottom right corner after `n` bytes */
+    fn shortest(&self, n: usize) -> Option<usize> {
+        let g = self.grid(n);
+        let (start, exit) = (Pos { x: 0, y: 0 }, Pos { x: self.width - 1, y: self.height - 1 });
+        if g.get(&start) != Some('.') { return None; }
+        bfs([start], |p| {
+            g.neighbors_xy(p).into_iter()
+                .filter(|(_, c)| *c == '.')
+                .map(|(p, _)| p)
+        }, |p| *p == exit).best()
+    }
+    /*
+     * The first byte that cuts the exit off. Once cut off it stays that
+     * way, so binary search for the smallest number of fallen bytes
+     * without a path.
+     */
+    fn blocking(&self) -> Option<Pos> {
+        let ns: Vec<usize> = (0..=self.bytes.len()).collect();
+        let n = ns.partition_point(|n| self.shortest(*n).is_some());
+        (n > 0 && n <= self.bytes.len()).then(|| self.bytes[n - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    #[test]
+    fn test_example() {
+        let m = Memory::parse(EXAMPLE).unwrap();
+        assert_eq!((m.width, m.height, m.fallen), (7, 7, 12));
+        assert_eq!(m.grid(12).to_string(), "
+...#...
+..#..#.
+....#..
+...#..#
+..#..#.
+.#..#..
+#.#....
+"[1..]);
+        assert_eq!(Day18::part1(&m).to_string(), "22");
+        assert_eq!(Day18::part2(&m).to_string(), "6,1");
+        assert_eq!(m.shortest(20), Some(24));
+        assert_eq!(m.shortest(21), None);
+    }
+
+    #[test]
+    fn test_header() {
+        let m = Memory::parse(&format!("size=7x7 bytes=21{EXAMPLE}")).unwrap();
+        assert_eq!(Day18::part1(&m).to_string(), "unreachable");
+        let m = Memory::parse("size=3x2\n1,0\n1,1").unwrap();
+        assert_eq!((m.width, m.height, m.fallen), (3, 2, 2));
+        assert_eq!(m.shortest(0), Some(3));
+        assert_eq!(m.blocking(), Some(Pos { x: 1, y: 1 }));
+        let m = Memory::parse("10,10\n").unwrap();
+        assert_eq!((m.width, m.height, m.fallen), (71, 71, 1));
+        assert_eq!(m.shortest(1), Some(140));
+        assert_eq!(Day18::part2(&m).to_string(), "none");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+5,4
+4;2
+";
+        assert_eq!(Memory::parse(input).err(), Some(ParseError::new(3, 1, "4;2", "'<x>,<y>'")));
+        let input = "
+size=7x7
+5,4
+7,2
+";
+        assert_eq!(Memory::parse(input).err(), Some(ParseError::new(4, 1, "7,2", "byte within 7x7")));
+        let input = "
+room=7x7
+5,4
+";
+        assert_eq!(Memory::parse(input).err(), Some(ParseError::new(2, 1, "room=7x7", "'size=<width>x<height>' or 'bytes=<n>'")));
+        assert_eq!(Memory::parse("size=-1x5").err(), Some(ParseError::new(1, 6, "-1x5", "positive '<width>x<height>'")));
+        assert_eq!(Memory::parse("size=7x0\n").err(), Some(ParseError::new(1, 6, "7x0", "positive '<width>x<height>'")));
+    }
+}
@@ -0,0 +1,217 @@
+use aoc_common::day::Solution;
+use aoc_common::parse::ParseError;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = Onsen;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        input.designs.iter().filter(|d| input.towels.matches(d)).count()
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        input.designs.iter()
+            .map(|d| input.towels.arrangements::<u128>(d))
+            .try_fold(0u128, |s, n| s.checked_add(n?))
+            .map_or("overflow".to_string(), |s| s.to_string())
+    }
+}
+
+/* towel patterns, sharing common prefixes */
+#[derive(Clone,Debug,Default)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone,Debug,Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    towel: bool,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Trie { nodes: vec![Node::default()] }
+    }
+    fn insert(&mut self, towel: &str) {
+        let mut n = 0;
+        for b in towel.bytes() {
+            n = match self.nodes[n].next.get(&b) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[n].next.insert(b, next);
+                    next
+                },
+            };
+        }
+        self.nodes[n].towel = true;
+    }
+    /* lengths of the towels that `design` starts with */
+    fn prefixes<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        design.iter()
+            .scan(0, |n, b| {
+                *n = *self.nodes[*n].next.get(b)?;
+                Some(*n)
+            })
+            .enumerate()
+            .filter(|(_, n)| self.nodes[*n].towel)
+            .map(|(i, _)| i + 1)
+    }
+    fn matches(&self, design: &str) -> bool {
+        let design = design.as_bytes();
+        let mut ok = vec![false; design.len() + 1];
+        ok[design.len()] = true;
+        for i in (0..design.len()).rev() {
+            ok[i] = self.prefixes(&design[i..]).any(|l| ok[i + l]);
+        }
+        ok[0]
+    }
+    /*
+     * Ways to lay out `design` with towels, `None` if that does not fit
+     * into `N`. Every suffix is counted once, from the back.
+     */
+    fn arrangements<N: Count>(&self, design: &str) -> Option<N> {
+        let design = design.as_bytes();
+        let mut ways = vec![N::default(); design.len() + 1];
+        ways[design.len()] = N::ONE;
+        for i in (0..design.len()).rev() {
+            for l in self.prefixes(&design[i..]) {
+                ways[i] = ways[i].checked(ways[i + l])?;
+            }
+        }
+        Some(ways[0])
+    }
+}
+
+/* counts that tell when they overflow */
+pub trait Count: Copy + Default + PartialEq {
+    const ONE: Self;
+    fn checked(self, n: Self) -> Option<Self>;
+}
+
+impl Count for u64 {
+    const ONE: Self = 1;
+    fn checked(self, n: Self) -> Option<Self> {
+        self.checked_add(n)
+    }
+}
+
+impl Count for u128 {
+    const ONE: Self = 1;
+    fn checked(self, n: Self) -> Option<Self> {
+        self.checked_add(n)
+    }
+}
+
+#[derive(Clone,Debug)]
+pub struct Onsen {
+    towels: Trie,
+    designs: Vec<String>,
+}
+
+const STRIPES: &str = "stripes 'w', 'u', 'b', 'r' or 'g'";
+
+fn stripes<'a>(input: &'a str, s: &'a str) -> Result<&'a str, ParseError> {
+    match s.find(|c| !"wubrg".contains(c)) {
+        Some(i) => Err(ParseError::at(input, &s[i..], STRIPES)),
+        None if s.is_empty() => Err(ParseError::at(input, s, STRIPES)),
+        None => Ok(s),
+    }
+}
+
+/*
+ * r, wr, b, g, bwu, rb, gb, br
+ *
+ * brwrr
+ * bggr
+ */
+fn parse(input: &str) -> Result<Onsen, ParseError> {
+    let mut lines = input.lines().filter(|l| !l.is_empty());
+    let mut towels = Trie::new();
+    let line = lines.next().ok_or(ParseError::end(input, "towel patterns"))?;
+    for towel in line.split(", ") {
+        towels.insert(stripes(input, towel)?);
+    }
+    let designs = lines
+        .map(|l| stripes(input, l).map(|d| d.to_string()))
+        .collect::<Result<Vec<String>, ParseError>>()?;
+    Ok(Onsen { towels, designs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn test_example() {
+        let o = parse(EXAMPLE).unwrap();
+        let ways: Vec<u64> = o.designs.iter().map(|d| o.towels.arrangements(d).unwrap()).collect();
+        assert_eq!(ways, vec![2, 1, 4, 6, 0, 1, 2, 0]);
+        assert_eq!(o.designs.iter().map(|d| o.towels.matches(d)).collect::<Vec<_>>(),
+            vec![true, true, true, true, false, true, true, false]);
+        assert_eq!(Day19::part1(&o).to_string(), "6");
+        assert_eq!(Day19::part2(&o).to_string(), "16");
+    }
+
+    #[test]
+    fn test_trie() {
+        let o = parse("r, rb, rbg, b\n").unwrap();
+        assert_eq!(o.towels.prefixes(b"rbgw").collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(o.towels.prefixes(b"br").collect::<Vec<_>>(), vec![1]);
+        assert_eq!(o.towels.prefixes(b"g").count(), 0);
+        assert_eq!(o.towels.arrangements::<u64>(""), Some(1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        /* laying out 'w' and 'ww' gives the fibonacci numbers */
+        let o = parse("w, ww\n").unwrap();
+        assert_eq!(o.towels.arrangements::<u64>(&"w".repeat(90)), Some(4660046610375530309));
+        assert_eq!(o.towels.arrangements::<u64>(&"w".repeat(100)), None);
+        assert_eq!(o.towels.arrangements::<u128>(&"w".repeat(100)), Some(573147844013817084101));
+        assert!(o.towels.matches(&"w".repeat(100)));
+        let o = parse(&format!("w, ww\n\n{}\n", "w".repeat(200))).unwrap();
+        assert_eq!(Day19::part2(&o).to_string(), "overflow");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+r, wx, b
+
+brwrr
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(2, 5, "x", STRIPES)));
+        let input = "
+r, , b
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(2, 4, "", STRIPES)));
+        let input = "
+r, b
+
+br
+bar
+";
+        assert_eq!(parse(input).err(), Some(ParseError::new(5, 2, "ar", STRIPES)));
+    }
+}
@@ -1,57 +1,66 @@
 use LKType::*;
+use aoc_common::day::Solution;
+use aoc_common::parse::ParseError;
 use itertools::Itertools;
-use std::fs;
-
-fn main() {
-    let file_path = "./input.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file");
-    let d = parse(&input)
-        .expect("Parse failed");
-    println!("{} / l{} k{}", d.fit().count(), d.locks.len(), d.keys.len());
+use std::fmt::Display;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Data;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> impl Display {
+        input.fit().count()
+    }
+    fn part2(input: &Self::Input) -> impl Display {
+        format!("l{} k{}", input.locks.len(), input.keys.len())
+    }
 }
 
-fn parse(input: &String) -> Option<Data> {
+fn parse(input: &str) -> Result<Data, ParseError> {
     let mut d = Data::new();
 
-    let mut lk_str = Vec::<String>::with_capacity(KEY_HEIGHT);
+    let mut lk_str = Vec::<&str>::with_capacity(KEY_HEIGHT + 2);
     for line in input.lines() {
         if line.is_empty() {
             continue;
         }
-        match line.chars().next().expect("no char")
-        {
-            '#' | '.' => {
-                lk_str.push(line.to_string());
-            },
-            _ => { continue },
+        if line.len() != KEY_LENGTH || line.contains(|c| c != '#' && c != '.') {
+            return Err(ParseError::at(input, line, format!("{} of '#' or '.'", KEY_LENGTH)));
         }
+        lk_str.push(line);
         if lk_str.len() >= KEY_HEIGHT + 2 {
             // println!("{:?}", lk_str);
             let mut seq = [0, 0, 0, 0, 0];
-            for i in 1..=KEY_HEIGHT {
-                for (pos, c) in lk_str[i].chars().enumerate() {
+            for line in &lk_str[1..=KEY_HEIGHT] {
+                for (pos, c) in line.chars().enumerate() {
                     match c {
                         '#' => seq[pos] += 1,
                         _ => continue,
                     }
                 }
             }
-            match lk_str[0].chars().next().expect("no char") {
-                '#' => d.locks.push(LK::new(Lock, seq)),
-                '.' => d.keys.push(LK::new(Key, seq)),
-                _ => panic!(),
+            match lk_str[0] {
+                "#####" => d.locks.push(LK::new(Lock, seq)),
+                "....." => d.keys.push(LK::new(Key, seq)),
+                top => return Err(ParseError::at(input, top, "lock '#####' or key '.....'")),
             }
             lk_str.clear();
         }
     }
+    if !lk_str.is_empty() {
+        return Err(ParseError::end(input, "schematic row"));
+    }
     // println!("{}", d);
-    Some(d)
+    Ok(d)
 }
 
 type Pair = (LK, LK);
 
-struct Data {
+pub struct Data {
     locks: Vec<LK>,
     keys: Vec<LK>,
 }
@@ -65,7 +74,7 @@ impl Data {
     }
     fn pairs(&self) -> impl Iterator<Item=Pair> {
         self.locks.clone().into_iter()
-            .cartesian_product(self.keys.clone().into_iter()).into_iter()
+            .cartesian_product(self.keys.clone())
     }
     fn fit(&self) -> impl Iterator<Item=Pair> {
         self.pairs().filter(|(l,k)| l.seq & k.seq == 0)
@@ -252,4 +261,29 @@ key  00000011000000001111000111001111
 lock 00011100011111000000011000010000
 key  00000001000000000011000000000111");
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "
+#####
+.####
+.###
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 1, ".###", "5 of '#' or '.'")));
+        let input = "
+#.###
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(2, 1, "#.###", "lock '#####' or key '.....'")));
+        let input = "
+#####
+.####
+".to_string();
+        assert_eq!(parse(&input).err(), Some(ParseError::new(4, 1, "", "schematic row")));
+    }
 }